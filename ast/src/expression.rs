@@ -1,6 +1,9 @@
 use core::fmt;
+use num_bigint::BigInt;
 use token::{Span, Token};
 
+use crate::statement::{BlockStatement, If};
+
 #[derive(Debug, Clone)]
 pub enum Expression {
     Identifier(Identifier),
@@ -9,6 +12,21 @@ pub enum Expression {
     Infix(BinaryExpression),
     FunctionCall(FunctionCall),
     UnaryOperator(UnaryOperator),
+    Assignment(Assignment),
+    If(Box<If>),
+    Block(BlockStatement),
+    Conditional(Conditional),
+    MethodCall(MethodCall),
+    Field(Field),
+    Array(Array),
+    Tuple(Tuple),
+    Hash(Hash),
+    Set(Set),
+    Index(Index),
+    Slice(Slice),
+    Spread(Spread),
+    ListComprehension(Box<ListComprehension>),
+    HashComprehension(Box<HashComprehension>),
 }
 
 #[derive(Debug, Clone)]
@@ -26,6 +44,21 @@ pub struct UnaryOperator {
     pub span: Span,
 }
 
+#[derive(Debug, Clone)]
+pub struct Assignment {
+    pub target: Box<Expression>,
+    pub value: Box<Expression>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub struct Conditional {
+    pub condition: Box<Expression>,
+    pub consequent: Box<Expression>,
+    pub alternate: Box<Expression>,
+    pub span: Span,
+}
+
 #[derive(Debug, Clone)]
 pub struct FunctionCall {
     pub call: Box<Expression>,
@@ -33,6 +66,38 @@ pub struct FunctionCall {
     pub span: Span,
 }
 
+#[derive(Debug, Clone)]
+pub struct MethodCall {
+    pub receiver: Box<Expression>,
+    pub method: Identifier,
+    pub arguments: Vec<Expression>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub struct Field {
+    pub receiver: Box<Expression>,
+    pub field: Identifier,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub struct Index {
+    pub left: Box<Expression>,
+    pub index: Box<Expression>,
+    pub span: Span,
+}
+
+// xs[start:end:step], every part of a slice can be left out.
+#[derive(Debug, Clone)]
+pub struct Slice {
+    pub left: Box<Expression>,
+    pub start: Option<Box<Expression>>,
+    pub end: Option<Box<Expression>>,
+    pub step: Option<Box<Expression>>,
+    pub span: Span,
+}
+
 #[derive(Debug, Clone)]
 pub struct Identifier {
     pub name: String,
@@ -42,8 +107,11 @@ pub struct Identifier {
 #[derive(Debug, Clone)]
 pub enum Literal {
     Integer(Integer),
+    BigInteger(BigInteger),
+    Decimal(Decimal),
     Boolean(Boolean),
     String(StringType),
+    Bytes(Bytes),
 }
 
 #[derive(Debug, Clone)]
@@ -67,12 +135,31 @@ pub struct Integer {
     pub span: Span,
 }
 
+#[derive(Debug, Clone)]
+pub struct BigInteger {
+    pub raw: BigInt,
+    pub span: Span,
+}
+
+// The digits of a 12.34d literal as they are written, without the suffix.
+#[derive(Debug, Clone)]
+pub struct Decimal {
+    pub raw: String,
+    pub span: Span,
+}
+
 #[derive(Debug, Clone)]
 pub struct Boolean {
     pub raw: bool,
     pub span: Span,
 }
 
+#[derive(Debug, Clone)]
+pub struct Bytes {
+    pub raw: Vec<u8>,
+    pub span: Span,
+}
+
 #[derive(Debug, Clone)]
 pub struct StringType {
     pub raw: String,
@@ -85,14 +172,69 @@ pub struct Array {
     pub span: Span,
 }
 
+#[derive(Debug, Clone)]
+pub struct Tuple {
+    pub elements: Vec<Expression>,
+    pub span: Span,
+}
+
 #[derive(Debug, Clone)]
 pub struct Hash {
-    pub pairs: Vec<(Expression, Expression)>,
+    pub entries: Vec<HashEntry>,
+    pub span: Span,
+}
+
+// #{a, b} holds every distinct element once.
+#[derive(Debug, Clone)]
+pub struct Set {
+    pub elements: Vec<Expression>,
     pub span: Span,
 }
 
-pub fn format_expressions(exprs: &Vec<Expression>) -> String {
-    exprs.iter().map(|expr| expr.to_string()).collect()
+#[derive(Debug, Clone)]
+pub enum HashEntry {
+    Pair(Expression, Expression),
+    // the pairs of another hash, the later entries overwrite its keys
+    Spread(Spread),
+}
+
+// ...xs expands to the elements of an iterable inside of the arguments of
+// a call and the elements of a collection literal.
+#[derive(Debug, Clone)]
+pub struct Spread {
+    pub argument: Box<Expression>,
+    pub span: Span,
+}
+
+// The `for x in xs if condition` clause shared by the comprehensions.
+#[derive(Debug, Clone)]
+pub struct Comprehension {
+    pub variable: Identifier,
+    pub iterable: Box<Expression>,
+    pub condition: Option<Box<Expression>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ListComprehension {
+    pub element: Box<Expression>,
+    pub comprehension: Comprehension,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub struct HashComprehension {
+    pub key: Box<Expression>,
+    pub value: Box<Expression>,
+    pub comprehension: Comprehension,
+    pub span: Span,
+}
+
+pub fn format_expressions(exprs: &[Expression]) -> String {
+    exprs
+        .iter()
+        .map(|expr| expr.to_string())
+        .collect::<Vec<String>>()
+        .join(", ")
 }
 
 impl fmt::Display for Identifier {
@@ -107,12 +249,30 @@ impl fmt::Display for Integer {
     }
 }
 
+impl fmt::Display for BigInteger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}d", self.raw)
+    }
+}
+
 impl fmt::Display for Boolean {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.raw)
     }
 }
 
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "b\"{}\"", self.raw.escape_ascii())
+    }
+}
+
 impl fmt::Display for StringType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.raw)
@@ -123,12 +283,27 @@ impl fmt::Display for Literal {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Literal::Integer(integer) => write!(f, "{}", integer),
+            Literal::BigInteger(integer) => write!(f, "{}", integer),
+            Literal::Decimal(decimal) => write!(f, "{}", decimal),
             Literal::Boolean(boolean) => write!(f, "{}", boolean),
             Literal::String(string_type) => write!(f, "{}", string_type),
+            Literal::Bytes(bytes) => write!(f, "{}", bytes),
         }
     }
 }
 
+impl fmt::Display for Comprehension {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "for {} in {}", self.variable, self.iterable)?;
+
+        if let Some(condition) = &self.condition {
+            write!(f, " if {}", condition)?;
+        }
+
+        Ok(())
+    }
+}
+
 impl fmt::Display for UnaryOperatorType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -145,6 +320,87 @@ impl fmt::Display for Expression {
         match self {
             Expression::UnaryOperator(unop) => write!(f, "{}{}", unop.identifer, unop.ty),
             Expression::Identifier(identifier) => write!(f, "{}", identifier.name),
+            Expression::Assignment(Assignment { target, value, .. }) => {
+                write!(f, "{} = {}", target, value)
+            }
+            Expression::If(if_expr) => write!(f, "{}", if_expr),
+            Expression::Block(block) => write!(f, "{{ {} }}", block),
+            Expression::Conditional(Conditional {
+                condition,
+                consequent,
+                alternate,
+                ..
+            }) => write!(f, "({} ? {} : {})", condition, consequent, alternate),
+            Expression::MethodCall(MethodCall {
+                receiver,
+                method,
+                arguments,
+                ..
+            }) => write!(
+                f,
+                "{}.{}({})",
+                receiver,
+                method,
+                format_expressions(arguments)
+            ),
+            Expression::Field(Field {
+                receiver, field, ..
+            }) => write!(f, "{}.{}", receiver, field),
+            Expression::Array(Array { elements, .. }) => {
+                write!(f, "[{}]", format_expressions(elements))
+            }
+            // a tuple of one element keeps its comma to not be read as a grouping
+            Expression::Tuple(Tuple { elements, .. }) if elements.len() == 1 => {
+                write!(f, "({},)", elements[0])
+            }
+            Expression::Tuple(Tuple { elements, .. }) => {
+                write!(f, "({})", format_expressions(elements))
+            }
+            Expression::Hash(Hash { entries, .. }) => write!(
+                f,
+                "{{{}}}",
+                entries
+                    .iter()
+                    .map(|entry| match entry {
+                        HashEntry::Pair(key, value) => format!("{}: {}", key, value),
+                        HashEntry::Spread(spread) => format!("...{}", spread.argument),
+                    })
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            Expression::Set(Set { elements, .. }) => {
+                write!(f, "#{{{}}}", format_expressions(elements))
+            }
+            Expression::Index(Index { left, index, .. }) => write!(f, "({}[{}])", left, index),
+            Expression::Spread(Spread { argument, .. }) => write!(f, "...{}", argument),
+            Expression::Slice(slice) => {
+                let bound = |bound: &Option<Box<Expression>>| {
+                    bound
+                        .as_ref()
+                        .map(|bound| bound.to_string())
+                        .unwrap_or_default()
+                };
+
+                write!(
+                    f,
+                    "({}[{}:{}",
+                    slice.left,
+                    bound(&slice.start),
+                    bound(&slice.end)
+                )?;
+
+                if slice.step.is_some() {
+                    write!(f, ":{}", bound(&slice.step))?;
+                }
+
+                write!(f, "])")
+            }
+            Expression::ListComprehension(list) => {
+                write!(f, "[{} {}]", list.element, list.comprehension)
+            }
+            Expression::HashComprehension(hash) => {
+                write!(f, "{{{}: {} {}}}", hash.key, hash.value, hash.comprehension)
+            }
             Expression::Literal(literal) => write!(f, "{}", literal),
             Expression::Prefix(UnaryExpression {
                 operand, operator, ..
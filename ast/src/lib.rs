@@ -5,13 +5,14 @@ use program::Program;
 use statement::Statement;
 
 pub mod expression;
+pub mod modify;
 pub mod program;
 pub mod statement;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Node {
     Program(Program),
-    Statement(Statement),
+    Statement(Box<Statement>),
     Expression(Expression),
 }
 
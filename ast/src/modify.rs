@@ -0,0 +1,361 @@
+use crate::{
+    expression::{
+        Array, Assignment, BinaryExpression, Comprehension, Conditional, Expression, Field,
+        FunctionCall, Hash, HashComprehension, HashEntry, Index, ListComprehension, MethodCall,
+        Set, Slice, Spread, Tuple, UnaryExpression,
+    },
+    statement::{
+        BlockStatement, Defer, For, ForIn, Function, If, Impl, Return, Statement, Trait,
+        TupleDeclaration, Variable, Yield,
+    },
+};
+use token::TokenKind;
+
+// What the walker does with the expression returned by Modifier::expression.
+pub enum Modified {
+    // the expression is used as it is
+    Replace(Expression),
+    // the children of the expression are modified too
+    Descend(Expression),
+}
+
+// A transformation of the syntax tree. The walker calls the expression hook
+// before visiting the children of every expression, and the declaration hook with
+// the names introduced by variables, parameters, functions and loops.
+pub trait Modifier {
+    type Error;
+
+    fn expression(&mut self, expr: Expression) -> Result<Modified, Self::Error> {
+        Ok(Modified::Descend(expr))
+    }
+
+    fn declaration(&mut self, name: String) -> String {
+        name
+    }
+}
+
+pub fn modify_statements<M: Modifier>(
+    statements: Vec<Statement>,
+    modifier: &mut M,
+) -> Result<Vec<Statement>, M::Error> {
+    statements
+        .into_iter()
+        .map(|statement| modify_statement(statement, modifier))
+        .collect()
+}
+
+pub fn modify_statement<M: Modifier>(
+    statement: Statement,
+    modifier: &mut M,
+) -> Result<Statement, M::Error> {
+    Ok(match statement {
+        Statement::VariableDeclaration(variable) => {
+            Statement::VariableDeclaration(modify_variable(variable, modifier)?)
+        }
+        Statement::ConstantDeclaration(constant) => {
+            Statement::ConstantDeclaration(modify_variable(constant, modifier)?)
+        }
+        Statement::TupleDeclaration(declaration) => Statement::TupleDeclaration(TupleDeclaration {
+            names: declaration
+                .names
+                .into_iter()
+                .map(|mut name| {
+                    name.name = modifier.declaration(name.name);
+                    name
+                })
+                .collect(),
+            expr: modify_expression(declaration.expr, modifier)?,
+            ..declaration
+        }),
+        Statement::Expression(expr) => Statement::Expression(modify_expression(expr, modifier)?),
+        Statement::If(if_stmt) => Statement::If(modify_if(if_stmt, modifier)?),
+        Statement::Return(Return { argument, span }) => Statement::Return(Return {
+            argument: modify_expression(argument, modifier)?,
+            span,
+        }),
+        Statement::Yield(Yield { argument, span }) => Statement::Yield(Yield {
+            argument: modify_expression(argument, modifier)?,
+            span,
+        }),
+        Statement::Defer(Defer { argument, span }) => Statement::Defer(Defer {
+            argument: modify_expression(argument, modifier)?,
+            span,
+        }),
+        Statement::Function(mut function) => {
+            function.name = modifier.declaration(function.name);
+            Statement::Function(modify_function(function, modifier)?)
+        }
+        Statement::Macro(function) => Statement::Macro(modify_function(function, modifier)?),
+        Statement::For(for_stmt) => Statement::For(For {
+            initializer: for_stmt
+                .initializer
+                .map(|variable| modify_variable(variable, modifier))
+                .transpose()?,
+            condition: for_stmt
+                .condition
+                .map(|condition| modify_expression(condition, modifier))
+                .transpose()?,
+            increment: for_stmt
+                .increment
+                .map(|increment| modify_expression(increment, modifier))
+                .transpose()?,
+            body: Box::new(modify_block(*for_stmt.body, modifier)?),
+            label: for_stmt.label,
+            span: for_stmt.span,
+        }),
+        Statement::ForIn(ForIn {
+            mut variable,
+            iterable,
+            body,
+            label,
+            span,
+        }) => {
+            variable.name = modifier.declaration(variable.name);
+
+            Statement::ForIn(ForIn {
+                variable,
+                iterable: modify_expression(iterable, modifier)?,
+                body: Box::new(modify_block(*body, modifier)?),
+                label,
+                span,
+            })
+        }
+        Statement::Block(block) => Statement::Block(modify_block(block, modifier)?),
+        jump @ (Statement::Break(_) | Statement::Continue(_)) => jump,
+        Statement::Struct(struct_stmt) => Statement::Struct(struct_stmt),
+        Statement::Impl(impl_stmt) => Statement::Impl(Impl {
+            methods: modify_functions(impl_stmt.methods, modifier)?,
+            ..impl_stmt
+        }),
+        Statement::Trait(trait_stmt) => Statement::Trait(Trait {
+            methods: modify_functions(trait_stmt.methods, modifier)?,
+            ..trait_stmt
+        }),
+    })
+}
+
+pub fn modify_block<M: Modifier>(
+    block: BlockStatement,
+    modifier: &mut M,
+) -> Result<BlockStatement, M::Error> {
+    Ok(BlockStatement {
+        body: modify_statements(block.body, modifier)?,
+        span: block.span,
+    })
+}
+
+fn modify_variable<M: Modifier>(
+    mut variable: Variable,
+    modifier: &mut M,
+) -> Result<Variable, M::Error> {
+    if let TokenKind::Identifier { name } = variable.identifier.kind {
+        variable.identifier.kind = TokenKind::Identifier {
+            name: modifier.declaration(name),
+        };
+    }
+
+    variable.expr = modify_expression(variable.expr, modifier)?;
+    Ok(variable)
+}
+
+// The name is left to the caller, since the names of the methods are not declarations.
+fn modify_function<M: Modifier>(
+    mut function: Function,
+    modifier: &mut M,
+) -> Result<Function, M::Error> {
+    for param in &mut function.params {
+        param.name = modifier.declaration(std::mem::take(&mut param.name));
+    }
+
+    function.body = Box::new(modify_block(*function.body, modifier)?);
+    Ok(function)
+}
+
+fn modify_functions<M: Modifier>(
+    functions: Vec<Function>,
+    modifier: &mut M,
+) -> Result<Vec<Function>, M::Error> {
+    functions
+        .into_iter()
+        .map(|function| modify_function(function, modifier))
+        .collect()
+}
+
+fn modify_if<M: Modifier>(if_stmt: If, modifier: &mut M) -> Result<If, M::Error> {
+    Ok(If {
+        condition: modify_expression(if_stmt.condition, modifier)?,
+        consequent: Box::new(modify_block(*if_stmt.consequent, modifier)?),
+        branches: if_stmt
+            .branches
+            .into_iter()
+            .map(|branch| modify_if(branch, modifier))
+            .collect::<Result<_, _>>()?,
+        alternate: if_stmt
+            .alternate
+            .map(|alternate| modify_block(*alternate, modifier).map(Box::new))
+            .transpose()?,
+        span: if_stmt.span,
+    })
+}
+
+fn modify_comprehension<M: Modifier>(
+    comprehension: Comprehension,
+    modifier: &mut M,
+) -> Result<Comprehension, M::Error> {
+    let mut variable = comprehension.variable;
+    variable.name = modifier.declaration(variable.name);
+
+    Ok(Comprehension {
+        variable,
+        iterable: modify_boxed(*comprehension.iterable, modifier)?,
+        condition: comprehension
+            .condition
+            .map(|condition| modify_boxed(*condition, modifier))
+            .transpose()?,
+    })
+}
+
+fn modify_boxed<M: Modifier>(
+    expr: Expression,
+    modifier: &mut M,
+) -> Result<Box<Expression>, M::Error> {
+    Ok(Box::new(modify_expression(expr, modifier)?))
+}
+
+fn modify_spread<M: Modifier>(spread: Spread, modifier: &mut M) -> Result<Spread, M::Error> {
+    Ok(Spread {
+        argument: modify_boxed(*spread.argument, modifier)?,
+        span: spread.span,
+    })
+}
+
+fn modify_bound<M: Modifier>(
+    bound: Option<Box<Expression>>,
+    modifier: &mut M,
+) -> Result<Option<Box<Expression>>, M::Error> {
+    bound
+        .map(|bound| modify_boxed(*bound, modifier))
+        .transpose()
+}
+
+fn modify_expressions<M: Modifier>(
+    exprs: Vec<Expression>,
+    modifier: &mut M,
+) -> Result<Vec<Expression>, M::Error> {
+    exprs
+        .into_iter()
+        .map(|expr| modify_expression(expr, modifier))
+        .collect()
+}
+
+pub fn modify_expression<M: Modifier>(
+    expr: Expression,
+    modifier: &mut M,
+) -> Result<Expression, M::Error> {
+    let expr = match modifier.expression(expr)? {
+        Modified::Replace(expr) => return Ok(expr),
+        Modified::Descend(expr) => expr,
+    };
+
+    Ok(match expr {
+        Expression::Identifier(_) | Expression::Literal(_) | Expression::UnaryOperator(_) => expr,
+        Expression::Prefix(prefix) => Expression::Prefix(UnaryExpression {
+            operand: modify_boxed(*prefix.operand, modifier)?,
+            ..prefix
+        }),
+        Expression::Infix(infix) => Expression::Infix(BinaryExpression {
+            left: modify_boxed(*infix.left, modifier)?,
+            right: modify_boxed(*infix.right, modifier)?,
+            ..infix
+        }),
+        Expression::FunctionCall(call) => Expression::FunctionCall(FunctionCall {
+            call: modify_boxed(*call.call, modifier)?,
+            arguments: modify_expressions(call.arguments, modifier)?,
+            span: call.span,
+        }),
+        Expression::Assignment(assignment) => Expression::Assignment(Assignment {
+            target: modify_boxed(*assignment.target, modifier)?,
+            value: modify_boxed(*assignment.value, modifier)?,
+            span: assignment.span,
+        }),
+        Expression::If(if_expr) => Expression::If(Box::new(modify_if(*if_expr, modifier)?)),
+        Expression::Block(block) => Expression::Block(modify_block(block, modifier)?),
+        Expression::Conditional(conditional) => Expression::Conditional(Conditional {
+            condition: modify_boxed(*conditional.condition, modifier)?,
+            consequent: modify_boxed(*conditional.consequent, modifier)?,
+            alternate: modify_boxed(*conditional.alternate, modifier)?,
+            span: conditional.span,
+        }),
+        Expression::MethodCall(call) => Expression::MethodCall(MethodCall {
+            receiver: modify_boxed(*call.receiver, modifier)?,
+            arguments: modify_expressions(call.arguments, modifier)?,
+            ..call
+        }),
+        Expression::Field(field) => Expression::Field(Field {
+            receiver: modify_boxed(*field.receiver, modifier)?,
+            ..field
+        }),
+        Expression::Array(array) => Expression::Array(Array {
+            elements: modify_expressions(array.elements, modifier)?,
+            span: array.span,
+        }),
+        Expression::Tuple(tuple) => Expression::Tuple(Tuple {
+            elements: modify_expressions(tuple.elements, modifier)?,
+            span: tuple.span,
+        }),
+        Expression::Set(set) => Expression::Set(Set {
+            elements: modify_expressions(set.elements, modifier)?,
+            span: set.span,
+        }),
+        Expression::Hash(hash) => Expression::Hash(Hash {
+            entries: hash
+                .entries
+                .into_iter()
+                .map(|entry| {
+                    Ok(match entry {
+                        HashEntry::Pair(key, value) => HashEntry::Pair(
+                            modify_expression(key, modifier)?,
+                            modify_expression(value, modifier)?,
+                        ),
+                        HashEntry::Spread(spread) => {
+                            HashEntry::Spread(modify_spread(spread, modifier)?)
+                        }
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+            span: hash.span,
+        }),
+        Expression::Index(index) => Expression::Index(Index {
+            left: modify_boxed(*index.left, modifier)?,
+            index: modify_boxed(*index.index, modifier)?,
+            span: index.span,
+        }),
+        Expression::Spread(spread) => Expression::Spread(modify_spread(spread, modifier)?),
+        Expression::Slice(slice) => Expression::Slice(Slice {
+            left: modify_boxed(*slice.left, modifier)?,
+            start: modify_bound(slice.start, modifier)?,
+            end: modify_bound(slice.end, modifier)?,
+            step: modify_bound(slice.step, modifier)?,
+            span: slice.span,
+        }),
+        Expression::ListComprehension(list) => {
+            let list = *list;
+
+            Expression::ListComprehension(Box::new(ListComprehension {
+                comprehension: modify_comprehension(list.comprehension, modifier)?,
+                element: modify_boxed(*list.element, modifier)?,
+                span: list.span,
+            }))
+        }
+        Expression::HashComprehension(hash) => {
+            let hash = *hash;
+
+            Expression::HashComprehension(Box::new(HashComprehension {
+                comprehension: modify_comprehension(hash.comprehension, modifier)?,
+                key: modify_boxed(*hash.key, modifier)?,
+                value: modify_boxed(*hash.value, modifier)?,
+                span: hash.span,
+            }))
+        }
+    })
+}
@@ -2,7 +2,7 @@ use std::fmt;
 use token::Span;
 use crate::statement::{format_statements, Statement};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Program {
     pub body: Vec<Statement>,
     pub span: Span,
@@ -6,14 +6,28 @@ use crate::expression::{Expression, Identifier};
 #[derive(Debug, Clone)]
 pub enum Statement {
     VariableDeclaration(Variable),
+    ConstantDeclaration(Variable),
     Expression(Expression),
     If(If),
     Return(Return),
+    Yield(Yield),
     Function(Function),
     For(For),
+    ForIn(ForIn),
+    Block(BlockStatement),
+    Struct(Struct),
+    Impl(Impl),
+    Defer(Defer),
+    Trait(Trait),
+    TupleDeclaration(TupleDeclaration),
+    // a macro is declared like a function, its parameters are bound to the
+    // quoted arguments and its body returns the quoted code of the expansion
+    Macro(Function),
+    Break(Break),
+    Continue(Continue),
 }
 
-pub fn format_statements(stmts: &Vec<Statement>) -> String {
+pub fn format_statements(stmts: &[Statement]) -> String {
     stmts.iter().map(|stmt| stmt.to_string()).collect()
 }
 
@@ -29,6 +43,17 @@ pub struct For {
     pub condition: Option<Expression>,
     pub increment: Option<Expression>,
     pub body: Box<BlockStatement>,
+    // set by `outer: for ...`, so break and continue can name the loop
+    pub label: Option<Identifier>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub struct ForIn {
+    pub variable: Identifier,
+    pub iterable: Expression,
+    pub body: Box<BlockStatement>,
+    pub label: Option<Identifier>,
     pub span: Span,
 }
 
@@ -37,6 +62,45 @@ pub struct Function {
     pub name: String,
     pub params: Vec<Identifier>,
     pub body: Box<BlockStatement>,
+    // the text of the doc comments written right before the function
+    pub doc: Option<String>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub struct Struct {
+    pub name: Identifier,
+    pub fields: Vec<Identifier>,
+    pub doc: Option<String>,
+    pub span: Span,
+}
+
+// The methods of an impl get the instance as their first parameter.
+#[derive(Debug, Clone)]
+pub struct Impl {
+    pub name: Identifier,
+    // set when the impl implements a trait for the struct
+    pub trait_name: Option<Identifier>,
+    pub methods: Vec<Function>,
+    pub span: Span,
+}
+
+// Every impl of a trait must define the required methods, the methods with
+// a body are the defaults of the ones the impl leaves out.
+#[derive(Debug, Clone)]
+pub struct Trait {
+    pub name: Identifier,
+    pub required: Vec<Signature>,
+    pub methods: Vec<Function>,
+    pub doc: Option<String>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub struct Signature {
+    pub name: String,
+    pub params: Vec<Identifier>,
+    pub doc: Option<String>,
     pub span: Span,
 }
 
@@ -53,6 +117,15 @@ pub struct Variable {
     pub span: Span,
 }
 
+// #(a, b) = expr; binds the values of a tuple to the names in order.
+#[derive(Debug, Clone)]
+pub struct TupleDeclaration {
+    pub names: Vec<Identifier>,
+    pub expr: Expression,
+    pub constant: bool,
+    pub span: Span,
+}
+
 #[derive(Debug, Clone)]
 pub struct If {
     pub condition: Expression,
@@ -62,14 +135,57 @@ pub struct If {
     pub span: Span,
 }
 
+// The argument of a defer is evaluated when the enclosing function returns.
+#[derive(Debug, Clone)]
+pub struct Defer {
+    pub argument: Expression,
+    pub span: Span,
+}
+
+// Leaves the loop with the label, or the innermost loop when there is no label.
+#[derive(Debug, Clone)]
+pub struct Break {
+    pub label: Option<Identifier>,
+    pub span: Span,
+}
+
+// Skips to the next iteration of the loop with the label, or of the innermost loop.
+#[derive(Debug, Clone)]
+pub struct Continue {
+    pub label: Option<Identifier>,
+    pub span: Span,
+}
+
 #[derive(Debug, Clone)]
 pub struct Return {
     pub argument: Expression,
     pub span: Span,
 }
 
+#[derive(Debug, Clone)]
+pub struct Yield {
+    pub argument: Expression,
+    pub span: Span,
+}
+
 impl fmt::Display for BlockStatement {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", format_statements(&self.body))
     }
 }
+
+impl fmt::Display for If {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "if {} {{ {} }}", self.condition, self.consequent)?;
+
+        for branch in &self.branches {
+            write!(f, " else {}", branch)?;
+        }
+
+        if let Some(alternate) = &self.alternate {
+            write!(f, " else {{ {} }}", alternate)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use object::object::Object;
+use std::rc::Rc;
+
+// Methods get their receiver as the first parameter, so the arity doesn't include it.
+pub fn expect_arity(name: &str, params: &[Rc<Object>], arity: usize) -> Result<(), Rc<Object>> {
+    if params.len() != arity + 1 {
+        return Err(Rc::from(Object::Error(format!(
+            "wrong number of arguments for {}! wanted {} got {}",
+            name,
+            arity,
+            params.len().saturating_sub(1)
+        ))));
+    }
+
+    Ok(())
+}
+
+pub fn expect_string<'a>(
+    name: &str,
+    params: &'a [Rc<Object>],
+    index: usize,
+) -> Result<&'a str, Rc<Object>> {
+    match params.get(index).map(|param| &**param) {
+        Some(Object::String(value)) => Ok(value),
+        Some(param) => Err(Rc::from(Object::Error(format!(
+            "expected a string as argument {} of {} but got {}",
+            index,
+            name,
+            param.type_name()
+        )))),
+        None => Err(Rc::from(Object::Error(format!(
+            "missing argument {} of {}",
+            index, name
+        )))),
+    }
+}
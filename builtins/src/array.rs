@@ -0,0 +1,89 @@
+use crate::arguments::{expect_arity, expect_string};
+use object::object::Object;
+use std::{cell::RefCell, rc::Rc};
+
+fn expect_array<'a>(
+    name: &str,
+    params: &'a [Rc<Object>],
+) -> Result<&'a RefCell<Vec<Rc<Object>>>, Rc<Object>> {
+    match params.first().map(|param| &**param) {
+        Some(Object::Array(elements)) => Ok(elements),
+        _ => Err(Rc::from(Object::Error(format!(
+            "{} can only be called on an array",
+            name
+        )))),
+    }
+}
+
+pub fn builtin_method_len(params: Vec<Rc<Object>>) -> Rc<Object> {
+    let elements = match expect_arity("len", &params, 0).and(expect_array("len", &params)) {
+        Ok(elements) => elements,
+        Err(e) => return e,
+    };
+
+    Rc::from(Object::Integer(elements.borrow().len() as i64))
+}
+
+// Appends the element to the array in place and returns the same array, so
+// the calls can be chained.
+pub fn builtin_method_push(params: Vec<Rc<Object>>) -> Rc<Object> {
+    let elements = match expect_arity("push", &params, 1).and(expect_array("push", &params)) {
+        Ok(elements) => elements,
+        Err(e) => return e,
+    };
+
+    elements.borrow_mut().push(Rc::clone(&params[1]));
+
+    Rc::clone(&params[0])
+}
+
+pub fn builtin_method_first(params: Vec<Rc<Object>>) -> Rc<Object> {
+    match expect_arity("first", &params, 0).and(expect_array("first", &params)) {
+        Ok(elements) => elements
+            .borrow()
+            .first()
+            .cloned()
+            .unwrap_or_else(|| Rc::from(Object::Null)),
+        Err(e) => e,
+    }
+}
+
+pub fn builtin_method_last(params: Vec<Rc<Object>>) -> Rc<Object> {
+    match expect_arity("last", &params, 0).and(expect_array("last", &params)) {
+        Ok(elements) => elements
+            .borrow()
+            .last()
+            .cloned()
+            .unwrap_or_else(|| Rc::from(Object::Null)),
+        Err(e) => e,
+    }
+}
+
+pub fn builtin_method_reverse(params: Vec<Rc<Object>>) -> Rc<Object> {
+    match expect_arity("reverse", &params, 0).and(expect_array("reverse", &params)) {
+        Ok(elements) => Rc::from(Object::array(
+            elements.borrow().iter().rev().cloned().collect(),
+        )),
+        Err(e) => e,
+    }
+}
+
+pub fn builtin_method_join(params: Vec<Rc<Object>>) -> Rc<Object> {
+    let elements = match expect_arity("join", &params, 1).and(expect_array("join", &params)) {
+        Ok(elements) => elements,
+        Err(e) => return e,
+    };
+
+    match expect_string("join", &params, 1) {
+        Ok(separator) => match elements
+            .borrow()
+            .iter()
+            .map(|element| element.to_text())
+            .collect::<Result<Vec<String>, _>>()
+        {
+            Ok(texts) => Rc::from(Object::String(texts.join(separator))),
+            Err(e) => Rc::from(Object::Error(e)),
+        },
+        Err(e) => e,
+    }
+}
@@ -0,0 +1,136 @@
+use crate::arguments::{expect_arity, expect_string};
+use object::object::Object;
+use std::rc::Rc;
+
+const BASE64_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn expect_bytes<'a>(name: &str, params: &'a [Rc<Object>]) -> Result<&'a [u8], Rc<Object>> {
+    match params.first().map(|param| &**param) {
+        Some(Object::Bytes(value)) => Ok(value),
+        _ => Err(Rc::from(Object::Error(format!(
+            "{} can only be called on bytes",
+            name
+        )))),
+    }
+}
+
+pub fn builtin_method_len(params: Vec<Rc<Object>>) -> Rc<Object> {
+    match expect_arity("len", &params, 0).and(expect_bytes("len", &params)) {
+        Ok(value) => Rc::from(Object::Integer(value.len() as i64)),
+        Err(e) => e,
+    }
+}
+
+pub fn builtin_method_hex(params: Vec<Rc<Object>>) -> Rc<Object> {
+    match expect_arity("hex", &params, 0).and(expect_bytes("hex", &params)) {
+        Ok(value) => Rc::from(Object::String(
+            value.iter().map(|byte| format!("{:02x}", byte)).collect(),
+        )),
+        Err(e) => e,
+    }
+}
+
+// Encodes the bytes with the standard base64 alphabet and its padding.
+pub fn builtin_method_base64(params: Vec<Rc<Object>>) -> Rc<Object> {
+    let value = match expect_arity("base64", &params, 0).and(expect_bytes("base64", &params)) {
+        Ok(value) => value,
+        Err(e) => return e,
+    };
+
+    let mut encoded = String::new();
+
+    for chunk in value.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (i, byte)| {
+            group | (*byte as u32) << (16 - i * 8)
+        });
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                let index = (group >> (18 - i * 6)) & 0b11_1111;
+                encoded.push(BASE64_ALPHABET[index as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+
+    Rc::from(Object::String(encoded))
+}
+
+// Turns the bytes into a string, the bytes that are not valid in the encoding
+// are reported instead of being replaced.
+pub fn builtin_method_decode(params: Vec<Rc<Object>>) -> Rc<Object> {
+    if let Err(e) = expect_arity("decode", &params, 1) {
+        return e;
+    }
+
+    let (value, encoding) = match (
+        expect_bytes("decode", &params),
+        expect_string("decode", &params, 1),
+    ) {
+        (Ok(value), Ok(encoding)) => (value, encoding),
+        (Err(e), _) | (_, Err(e)) => return e,
+    };
+
+    let decoded = match encoding {
+        "utf-8" => String::from_utf8(value.to_vec())
+            .map_err(|e| format!("bytes are not valid utf-8: {}", e.utf8_error())),
+        "ascii" => match value.iter().position(|byte| !byte.is_ascii()) {
+            Some(i) => Err(format!("byte {} at {} is not valid ascii", value[i], i)),
+            None => Ok(value.iter().map(|byte| *byte as char).collect()),
+        },
+        "latin-1" => Ok(value.iter().map(|byte| *byte as char).collect()),
+        _ => Err(unknown_encoding(encoding)),
+    };
+
+    match decoded {
+        Ok(decoded) => Rc::from(Object::String(decoded)),
+        Err(e) => Rc::from(Object::Error(e)),
+    }
+}
+
+// The encode method of the strings, the counterpart of decode.
+pub fn builtin_method_encode(params: Vec<Rc<Object>>) -> Rc<Object> {
+    if let Err(e) = expect_arity("encode", &params, 1) {
+        return e;
+    }
+
+    let (value, encoding) = match (
+        expect_string("encode", &params, 0),
+        expect_string("encode", &params, 1),
+    ) {
+        (Ok(value), Ok(encoding)) => (value, encoding),
+        (Err(e), _) | (_, Err(e)) => return e,
+    };
+
+    let limit = match encoding {
+        "utf-8" => return Rc::from(Object::Bytes(value.as_bytes().to_vec())),
+        "ascii" => 0x7f,
+        "latin-1" => 0xff,
+        _ => return Rc::from(Object::Error(unknown_encoding(encoding))),
+    };
+
+    match value
+        .chars()
+        .map(|ch| {
+            u8::try_from(ch)
+                .ok()
+                .filter(|byte| *byte <= limit)
+                .ok_or(ch)
+        })
+        .collect::<Result<Vec<u8>, char>>()
+    {
+        Ok(encoded) => Rc::from(Object::Bytes(encoded)),
+        Err(ch) => Rc::from(Object::Error(format!(
+            "character {} can not be encoded as {}",
+            ch, encoding
+        ))),
+    }
+}
+
+fn unknown_encoding(encoding: &str) -> String {
+    format!(
+        "unknown encoding {}, expected utf-8, ascii or latin-1",
+        encoding
+    )
+}
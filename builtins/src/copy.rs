@@ -0,0 +1,99 @@
+use object::{instance::Instance, object::Object};
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+fn single_param(name: &str, params: &[Rc<Object>]) -> Result<Rc<Object>, Rc<Object>> {
+    match params {
+        [param] => Ok(Rc::clone(param)),
+        _ => Err(Rc::from(Object::Error(format!(
+            "wrong number of arguments for {}! wanted 1 got {}",
+            name,
+            params.len()
+        )))),
+    }
+}
+
+// Returns a new array, hash or instance holding the same values, the values
+// that can't be changed are returned as they are.
+pub fn builtin_func_copy(params: Vec<Rc<Object>>) -> Rc<Object> {
+    let value = match single_param("copy", &params) {
+        Ok(value) => value,
+        Err(e) => return e,
+    };
+
+    match &*value {
+        Object::Array(elements) => Rc::from(Object::array(elements.borrow().clone())),
+        Object::Hash(pairs) => Rc::from(Object::hash(pairs.borrow().clone())),
+        Object::Set(elements) => Rc::from(Object::set(elements.borrow().clone())),
+        Object::Instance(instance) => Rc::from(Object::Instance(Rc::new(Instance {
+            ty: Rc::clone(&instance.ty),
+            values: RefCell::new(instance.values.borrow().clone()),
+        }))),
+        _ => value,
+    }
+}
+
+// Copies the collections nested in the value too. A collection reachable
+// more than once is copied once, so the shared and cyclic values keep their shape.
+pub fn builtin_func_deep_copy(params: Vec<Rc<Object>>) -> Rc<Object> {
+    match single_param("deep_copy", &params) {
+        Ok(value) => deep_copy(&value, &mut HashMap::new()),
+        Err(e) => e,
+    }
+}
+
+fn deep_copy(value: &Rc<Object>, copies: &mut HashMap<*const Object, Rc<Object>>) -> Rc<Object> {
+    if let Some(copy) = copies.get(&Rc::as_ptr(value)) {
+        return Rc::clone(copy);
+    }
+
+    // the copy is recorded before its values are copied, so a cycle ends at it
+    let copy = match &**value {
+        Object::Array(_) => Rc::from(Object::array(vec![])),
+        Object::Hash(_) => Rc::from(Object::hash(Default::default())),
+        // the elements of a set can't be changed, so they are not copied again
+        Object::Set(elements) => Rc::from(Object::set(elements.borrow().clone())),
+        Object::Instance(instance) => Rc::from(Object::Instance(Rc::new(Instance {
+            ty: Rc::clone(&instance.ty),
+            values: RefCell::new(vec![]),
+        }))),
+        Object::Tuple(elements) => {
+            return Rc::from(Object::Tuple(
+                elements
+                    .iter()
+                    .map(|element| deep_copy(element, copies))
+                    .collect(),
+            ))
+        }
+        _ => return Rc::clone(value),
+    };
+
+    copies.insert(Rc::as_ptr(value), Rc::clone(&copy));
+
+    match (&**value, &*copy) {
+        (Object::Array(elements), Object::Array(copied)) => {
+            *copied.borrow_mut() = elements
+                .borrow()
+                .iter()
+                .map(|element| deep_copy(element, copies))
+                .collect();
+        }
+        (Object::Hash(pairs), Object::Hash(copied)) => {
+            *copied.borrow_mut() = pairs
+                .borrow()
+                .iter()
+                .map(|(key, value)| (key.clone(), deep_copy(value, copies)))
+                .collect();
+        }
+        (Object::Instance(instance), Object::Instance(copied)) => {
+            *copied.values.borrow_mut() = instance
+                .values
+                .borrow()
+                .iter()
+                .map(|value| deep_copy(value, copies))
+                .collect();
+        }
+        _ => {}
+    }
+
+    copy
+}
@@ -0,0 +1,131 @@
+use num_bigint::Sign;
+use object::{
+    decimal::{Decimal, Rounding, MAX_SCALE},
+    object::Object,
+};
+use std::rc::Rc;
+
+// Makes a decimal of a string like "12.34" or of an integer.
+pub fn builtin_func_decimal(params: Vec<Rc<Object>>) -> Rc<Object> {
+    let [value] = params.as_slice() else {
+        return Rc::from(Object::Error(format!(
+            "wrong number of arguments for decimal! wanted 1 got {}",
+            params.len()
+        )));
+    };
+
+    let decimal = match &**value {
+        Object::String(text) => Decimal::parse(text),
+        Object::Integer(value) => Ok(Decimal::from_integer((*value).into())),
+        Object::BigInteger(value) => Ok(Decimal::from_integer(value.clone())),
+        Object::Decimal(_) => return Rc::clone(value),
+        value => Err(format!(
+            "expected a string or an integer as argument 0 of decimal but got {}",
+            value.type_name()
+        )),
+    };
+
+    match decimal {
+        Ok(decimal) => Rc::from(Object::Decimal(decimal)),
+        Err(e) => Rc::from(Object::Error(e)),
+    }
+}
+
+// Rounds to the given places after the point, half_even unless another
+// rounding mode is given.
+pub fn builtin_method_round(params: Vec<Rc<Object>>) -> Rc<Object> {
+    let Some(Object::Decimal(value)) = params.first().map(|param| &**param) else {
+        return Rc::from(Object::Error(
+            "round can only be called on a decimal".to_string(),
+        ));
+    };
+
+    match scale_and_rounding("round", &params[1..]) {
+        Ok((scale, rounding)) => Rc::from(Object::Decimal(value.round(scale, rounding))),
+        Err(e) => Rc::from(Object::Error(e)),
+    }
+}
+
+// Divides by a decimal or an integer to the given places after the point.
+pub fn builtin_method_div(params: Vec<Rc<Object>>) -> Rc<Object> {
+    let Some(Object::Decimal(value)) = params.first().map(|param| &**param) else {
+        return Rc::from(Object::Error(
+            "div can only be called on a decimal".to_string(),
+        ));
+    };
+
+    let divisor = match params.get(1).map(|param| &**param) {
+        Some(Object::Decimal(divisor)) => divisor.clone(),
+        Some(Object::Integer(divisor)) => Decimal::from_integer((*divisor).into()),
+        Some(Object::BigInteger(divisor)) => Decimal::from_integer(divisor.clone()),
+        Some(divisor) => {
+            return Rc::from(Object::Error(format!(
+                "expected a decimal or an integer as argument 0 of div but got {}",
+                divisor.type_name()
+            )))
+        }
+        None => {
+            return Rc::from(Object::Error(
+                "wrong number of arguments for div! wanted 2 or 3 got 0".to_string(),
+            ))
+        }
+    };
+
+    let result = scale_and_rounding("div", &params[2..])
+        .and_then(|(scale, rounding)| value.div(&divisor, scale, rounding));
+
+    match result {
+        Ok(result) => Rc::from(Object::Decimal(result)),
+        Err(e) => Rc::from(Object::Error(e)),
+    }
+}
+
+fn scale_and_rounding(name: &str, params: &[Rc<Object>]) -> Result<(u32, Rounding), String> {
+    let (scale, rounding) = match params {
+        [scale] => (scale, None),
+        [scale, rounding] => (scale, Some(rounding)),
+        _ => {
+            return Err(format!(
+                "expected the places and optionally the rounding mode for {}",
+                name
+            ))
+        }
+    };
+
+    let scale = match &**scale {
+        Object::Integer(scale) if *scale < 0 => {
+            return Err(format!("the places of {} can not be negative", name))
+        }
+        Object::BigInteger(scale) if scale.sign() == Sign::Minus => {
+            return Err(format!("the places of {} can not be negative", name))
+        }
+        Object::Integer(scale) if *scale <= MAX_SCALE.into() => *scale as u32,
+        Object::Integer(_) | Object::BigInteger(_) => {
+            return Err(format!(
+                "the places of {} can not be more than {}",
+                name, MAX_SCALE
+            ))
+        }
+        scale => {
+            return Err(format!(
+                "expected an integer as the places of {} but got {}",
+                name,
+                scale.type_name()
+            ))
+        }
+    };
+
+    let rounding = match rounding.map(|rounding| &**rounding) {
+        None => Rounding::HalfEven,
+        Some(Object::String(mode)) => Rounding::from_name(mode)?,
+        Some(mode) => {
+            return Err(format!(
+                "expected a string as the rounding mode of {} but got {}",
+                name,
+                mode.type_name()
+            ))
+        }
+    };
+
+    Ok((scale, rounding))
+}
@@ -0,0 +1,45 @@
+use object::object::Object;
+use std::{fs, rc::Rc};
+
+// Reads the file as it is, so the data that is not text is kept intact.
+pub fn builtin_func_read_bytes(params: Vec<Rc<Object>>) -> Rc<Object> {
+    let [path] = params.as_slice() else {
+        return Rc::from(Object::Error(format!(
+            "wrong number of arguments for read_bytes! wanted 1 got {}",
+            params.len()
+        )));
+    };
+
+    let Object::String(path) = &**path else {
+        return Rc::from(Object::Error(format!(
+            "expected a string as argument 0 of read_bytes but got {}",
+            path.type_name()
+        )));
+    };
+
+    match fs::read(path) {
+        Ok(content) => Rc::from(Object::Bytes(content)),
+        Err(e) => Rc::from(Object::Error(format!("could not read {}: {}", path, e))),
+    }
+}
+
+// Writes the bytes to the file, replacing what the file had.
+pub fn builtin_func_write_bytes(params: Vec<Rc<Object>>) -> Rc<Object> {
+    let [path, content] = params.as_slice() else {
+        return Rc::from(Object::Error(format!(
+            "wrong number of arguments for write_bytes! wanted 2 got {}",
+            params.len()
+        )));
+    };
+
+    let (Object::String(path), Object::Bytes(content)) = (&**path, &**content) else {
+        return Rc::from(Object::Error(
+            "write_bytes expects a path and bytes".to_string(),
+        ));
+    };
+
+    match fs::write(path, content) {
+        Ok(_) => Rc::from(Object::Null),
+        Err(e) => Rc::from(Object::Error(format!("could not write {}: {}", path, e))),
+    }
+}
@@ -0,0 +1,51 @@
+use crate::arguments::expect_arity;
+use object::{hash::HashKey, object::Object};
+use std::{cell::Ref, collections::BTreeMap, rc::Rc};
+
+fn expect_hash<'a>(
+    name: &str,
+    params: &'a [Rc<Object>],
+) -> Result<Ref<'a, BTreeMap<HashKey, Rc<Object>>>, Rc<Object>> {
+    match params.first().map(|param| &**param) {
+        Some(Object::Hash(pairs)) => Ok(pairs.borrow()),
+        _ => Err(Rc::from(Object::Error(format!(
+            "{} can only be called on a hash",
+            name
+        )))),
+    }
+}
+
+pub fn builtin_method_len(params: Vec<Rc<Object>>) -> Rc<Object> {
+    match expect_arity("len", &params, 0).and(expect_hash("len", &params)) {
+        Ok(pairs) => Rc::from(Object::Integer(pairs.len() as i64)),
+        Err(e) => e,
+    }
+}
+
+pub fn builtin_method_keys(params: Vec<Rc<Object>>) -> Rc<Object> {
+    match expect_arity("keys", &params, 0).and(expect_hash("keys", &params)) {
+        Ok(pairs) => Rc::from(Object::array(
+            pairs.keys().map(|key| Rc::from(key.to_object())).collect(),
+        )),
+        Err(e) => e,
+    }
+}
+
+pub fn builtin_method_values(params: Vec<Rc<Object>>) -> Rc<Object> {
+    match expect_arity("values", &params, 0).and(expect_hash("values", &params)) {
+        Ok(pairs) => Rc::from(Object::array(pairs.values().cloned().collect())),
+        Err(e) => e,
+    }
+}
+
+pub fn builtin_method_contains(params: Vec<Rc<Object>>) -> Rc<Object> {
+    let pairs = match expect_arity("contains", &params, 1).and(expect_hash("contains", &params)) {
+        Ok(pairs) => pairs,
+        Err(e) => return e,
+    };
+
+    match HashKey::from_object(&params[1]) {
+        Ok(key) => Rc::from(Object::Boolean(pairs.contains_key(&key))),
+        Err(e) => Rc::from(Object::Error(e)),
+    }
+}
@@ -0,0 +1,60 @@
+use crate::arguments::expect_arity;
+use num_bigint::BigInt;
+use object::object::Object;
+use std::rc::Rc;
+
+fn big_integer(obj: &Object) -> Option<BigInt> {
+    match obj {
+        Object::Integer(value) => Some(BigInt::from(*value)),
+        Object::BigInteger(value) => Some(value.clone()),
+        _ => None,
+    }
+}
+
+fn integer_object(value: BigInt) -> Rc<Object> {
+    Rc::from(match i64::try_from(&value) {
+        Ok(value) => Object::Integer(value),
+        Err(_) => Object::BigInteger(value),
+    })
+}
+
+// Returns the quotient and the remainder of the division as a tuple, the
+// quotient is truncated like the result of the / operator.
+pub fn builtin_func_divmod(params: Vec<Rc<Object>>) -> Rc<Object> {
+    let [left, right] = params.as_slice() else {
+        return Rc::from(Object::Error(format!(
+            "wrong number of arguments for divmod! wanted 2 got {}",
+            params.len()
+        )));
+    };
+
+    let (Some(left), Some(right)) = (big_integer(left), big_integer(right)) else {
+        return Rc::from(Object::Error("divmod expects two integers".to_string()));
+    };
+
+    if right == BigInt::ZERO {
+        return Rc::from(Object::Error("division by zero".to_string()));
+    }
+
+    Rc::from(Object::Tuple(vec![
+        integer_object(&left / &right),
+        integer_object(left % right),
+    ]))
+}
+
+pub fn builtin_method_abs(params: Vec<Rc<Object>>) -> Rc<Object> {
+    if let Err(e) = expect_arity("abs", &params, 0) {
+        return e;
+    }
+
+    match &*params[0] {
+        Object::Integer(value) => Rc::from(match value.checked_abs() {
+            Some(value) => Object::Integer(value),
+            None => Object::BigInteger(BigInt::from(*value).magnitude().clone().into()),
+        }),
+        Object::BigInteger(value) => Rc::from(Object::BigInteger(value.magnitude().clone().into())),
+        _ => Rc::from(Object::Error(
+            "abs can only be called on an integer".to_string(),
+        )),
+    }
+}
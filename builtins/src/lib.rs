@@ -1,15 +1,128 @@
 use macros::BuiltinHashMap;
+use object::object::{BuiltinFunc, Object};
 use std::{collections::HashMap, sync::LazyLock};
 
+pub mod arguments;
+pub mod array;
+pub mod bytes;
+pub mod copy;
+pub mod decimal;
+pub mod file;
+pub mod hash;
+pub mod integer;
 pub mod macros;
 pub mod object_converter;
+pub mod quote;
+pub mod set;
 pub mod stdio;
+pub mod string;
+pub mod traits;
 
 pub static BUILT_INS: LazyLock<BuiltinHashMap> = LazyLock::new(|| {
     builtin_builder! {
         "print" => stdio::builtin_func_print,
         "println" => stdio::builtin_func_println,
         "input" => stdio::builtin_func_input,
-        "clear" => stdio::builtin_func_clear_screen
+        "clear" => stdio::builtin_func_clear_screen,
+        "implements" => traits::builtin_func_implements,
+        "divmod" => integer::builtin_func_divmod,
+        "decimal" => decimal::builtin_func_decimal,
+        "copy" => copy::builtin_func_copy,
+        "deep_copy" => copy::builtin_func_deep_copy,
+        "read_bytes" => file::builtin_func_read_bytes,
+        "write_bytes" => file::builtin_func_write_bytes
     }
 });
+
+pub static STRING_METHODS: LazyLock<BuiltinHashMap> = LazyLock::new(|| {
+    builtin_builder! {
+        "len" => string::builtin_method_len,
+        "upper" => string::builtin_method_upper,
+        "lower" => string::builtin_method_lower,
+        "trim" => string::builtin_method_trim,
+        "contains" => string::builtin_method_contains,
+        "starts_with" => string::builtin_method_starts_with,
+        "ends_with" => string::builtin_method_ends_with,
+        "split" => string::builtin_method_split,
+        "replace" => string::builtin_method_replace,
+        "encode" => bytes::builtin_method_encode
+    }
+});
+
+pub static BYTES_METHODS: LazyLock<BuiltinHashMap> = LazyLock::new(|| {
+    builtin_builder! {
+        "len" => bytes::builtin_method_len,
+        "hex" => bytes::builtin_method_hex,
+        "base64" => bytes::builtin_method_base64,
+        "decode" => bytes::builtin_method_decode
+    }
+});
+
+pub static ARRAY_METHODS: LazyLock<BuiltinHashMap> = LazyLock::new(|| {
+    builtin_builder! {
+        "len" => array::builtin_method_len,
+        "push" => array::builtin_method_push,
+        "first" => array::builtin_method_first,
+        "last" => array::builtin_method_last,
+        "reverse" => array::builtin_method_reverse,
+        "join" => array::builtin_method_join
+    }
+});
+
+pub static HASH_METHODS: LazyLock<BuiltinHashMap> = LazyLock::new(|| {
+    builtin_builder! {
+        "len" => hash::builtin_method_len,
+        "keys" => hash::builtin_method_keys,
+        "values" => hash::builtin_method_values,
+        "contains" => hash::builtin_method_contains
+    }
+});
+
+pub static SET_METHODS: LazyLock<BuiltinHashMap> = LazyLock::new(|| {
+    builtin_builder! {
+        "len" => set::builtin_method_len,
+        "contains" => set::builtin_method_contains,
+        "add" => set::builtin_method_add,
+        "remove" => set::builtin_method_remove,
+        "union" => set::builtin_method_union,
+        "intersection" => set::builtin_method_intersection,
+        "difference" => set::builtin_method_difference
+    }
+});
+
+pub static INTEGER_METHODS: LazyLock<BuiltinHashMap> = LazyLock::new(|| {
+    builtin_builder! {
+        "abs" => integer::builtin_method_abs
+    }
+});
+
+pub static DECIMAL_METHODS: LazyLock<BuiltinHashMap> = LazyLock::new(|| {
+    builtin_builder! {
+        "round" => decimal::builtin_method_round,
+        "div" => decimal::builtin_method_div
+    }
+});
+
+pub static QUOTE_METHODS: LazyLock<BuiltinHashMap> = LazyLock::new(|| {
+    builtin_builder! {
+        "source" => quote::builtin_method_source
+    }
+});
+
+// Looks up a builtin method by the type of its receiver,
+// the receiver is passed to the method as its first parameter.
+pub fn lookup_method(receiver: &Object, name: &str) -> Option<BuiltinFunc> {
+    let methods = match receiver {
+        Object::String(_) => &STRING_METHODS,
+        Object::Bytes(_) => &BYTES_METHODS,
+        Object::Array(_) => &ARRAY_METHODS,
+        Object::Hash(_) => &HASH_METHODS,
+        Object::Set(_) => &SET_METHODS,
+        Object::Integer(_) | Object::BigInteger(_) => &INTEGER_METHODS,
+        Object::Decimal(_) => &DECIMAL_METHODS,
+        Object::Quote(_) => &QUOTE_METHODS,
+        _ => return None,
+    };
+
+    methods.get(name).copied()
+}
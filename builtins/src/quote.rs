@@ -0,0 +1,17 @@
+use crate::arguments::expect_arity;
+use object::object::Object;
+use std::rc::Rc;
+
+// The source text of the quoted code, handy for the messages of a macro.
+pub fn builtin_method_source(params: Vec<Rc<Object>>) -> Rc<Object> {
+    if let Err(e) = expect_arity("source", &params, 0) {
+        return e;
+    }
+
+    match &*params[0] {
+        Object::Quote(node) => Rc::from(Object::String(node.to_string())),
+        _ => Rc::from(Object::Error(
+            "source can only be called on a quote".to_string(),
+        )),
+    }
+}
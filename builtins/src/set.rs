@@ -0,0 +1,110 @@
+use crate::arguments::expect_arity;
+use object::{hash::HashKey, object::Object};
+use std::{cell::RefCell, collections::BTreeSet, rc::Rc};
+
+fn expect_set<'a>(
+    name: &str,
+    params: &'a [Rc<Object>],
+) -> Result<&'a RefCell<BTreeSet<HashKey>>, Rc<Object>> {
+    match params.first().map(|param| &**param) {
+        Some(Object::Set(elements)) => Ok(elements),
+        _ => Err(Rc::from(Object::Error(format!(
+            "{} can only be called on a set",
+            name
+        )))),
+    }
+}
+
+fn expect_element(params: &[Rc<Object>]) -> Result<HashKey, Rc<Object>> {
+    HashKey::from_set_element(&params[1]).map_err(|e| Rc::from(Object::Error(e)))
+}
+
+// The algebra methods take the other set as their argument and return a new set.
+fn set_operation(
+    name: &str,
+    params: &[Rc<Object>],
+    operation: fn(&BTreeSet<HashKey>, &BTreeSet<HashKey>) -> BTreeSet<HashKey>,
+) -> Rc<Object> {
+    let elements = match expect_arity(name, params, 1).and(expect_set(name, params)) {
+        Ok(elements) => elements,
+        Err(e) => return e,
+    };
+
+    match &*params[1] {
+        Object::Set(other) => Rc::from(Object::set(operation(&elements.borrow(), &other.borrow()))),
+        other => Rc::from(Object::Error(format!(
+            "expected a set as argument 1 of {} but got {}",
+            name,
+            other.type_name()
+        ))),
+    }
+}
+
+pub fn builtin_method_len(params: Vec<Rc<Object>>) -> Rc<Object> {
+    match expect_arity("len", &params, 0).and(expect_set("len", &params)) {
+        Ok(elements) => Rc::from(Object::Integer(elements.borrow().len() as i64)),
+        Err(e) => e,
+    }
+}
+
+pub fn builtin_method_contains(params: Vec<Rc<Object>>) -> Rc<Object> {
+    let elements = match expect_arity("contains", &params, 1).and(expect_set("contains", &params)) {
+        Ok(elements) => elements,
+        Err(e) => return e,
+    };
+
+    match expect_element(&params) {
+        Ok(element) => Rc::from(Object::Boolean(elements.borrow().contains(&element))),
+        Err(e) => e,
+    }
+}
+
+// Adds the element to the set in place and returns the same set, like push of the arrays.
+pub fn builtin_method_add(params: Vec<Rc<Object>>) -> Rc<Object> {
+    let elements = match expect_arity("add", &params, 1).and(expect_set("add", &params)) {
+        Ok(elements) => elements,
+        Err(e) => return e,
+    };
+
+    match expect_element(&params) {
+        Ok(element) => {
+            elements.borrow_mut().insert(element);
+            Rc::clone(&params[0])
+        }
+        Err(e) => e,
+    }
+}
+
+// Removes the element from the set in place, an element that isn't in the set is ignored.
+pub fn builtin_method_remove(params: Vec<Rc<Object>>) -> Rc<Object> {
+    let elements = match expect_arity("remove", &params, 1).and(expect_set("remove", &params)) {
+        Ok(elements) => elements,
+        Err(e) => return e,
+    };
+
+    match expect_element(&params) {
+        Ok(element) => {
+            elements.borrow_mut().remove(&element);
+            Rc::clone(&params[0])
+        }
+        Err(e) => e,
+    }
+}
+
+pub fn builtin_method_union(params: Vec<Rc<Object>>) -> Rc<Object> {
+    set_operation("union", &params, |left, right| {
+        left.union(right).cloned().collect()
+    })
+}
+
+pub fn builtin_method_intersection(params: Vec<Rc<Object>>) -> Rc<Object> {
+    set_operation("intersection", &params, |left, right| {
+        left.intersection(right).cloned().collect()
+    })
+}
+
+pub fn builtin_method_difference(params: Vec<Rc<Object>>) -> Rc<Object> {
+    set_operation("difference", &params, |left, right| {
+        left.difference(right).cloned().collect()
+    })
+}
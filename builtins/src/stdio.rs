@@ -3,8 +3,13 @@ use std::io::Write;
 use std::rc::Rc;
 
 pub fn builtin_func_print(params: Vec<Rc<Object>>) -> Rc<Object> {
-    params.iter().for_each(|obj| {
-        print!("{}", obj);
+    let texts = match to_texts(&params) {
+        Ok(texts) => texts,
+        Err(e) => return e,
+    };
+
+    texts.iter().for_each(|text| {
+        print!("{}", text);
         std::io::stdout().flush().unwrap();
     });
 
@@ -12,13 +17,28 @@ pub fn builtin_func_print(params: Vec<Rc<Object>>) -> Rc<Object> {
 }
 
 pub fn builtin_func_println(params: Vec<Rc<Object>>) -> Rc<Object> {
-    params.iter().for_each(|obj| {
-        println!("{}", obj);
+    let texts = match to_texts(&params) {
+        Ok(texts) => texts,
+        Err(e) => return e,
+    };
+
+    texts.iter().for_each(|text| {
+        println!("{}", text);
     });
 
     Rc::from(Object::Null)
 }
 
+// All of the values are converted before anything is printed, so a failing
+// to_string method doesn't leave half of the output behind.
+fn to_texts(params: &[Rc<Object>]) -> Result<Vec<String>, Rc<Object>> {
+    params
+        .iter()
+        .map(|obj| obj.to_text())
+        .collect::<Result<Vec<String>, _>>()
+        .map_err(|e| Rc::new(Object::Error(e)))
+}
+
 pub fn builtin_func_input(_params: Vec<Rc<Object>>) -> Rc<Object> {
     let mut s = String::new();
 
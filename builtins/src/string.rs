@@ -0,0 +1,101 @@
+use crate::arguments::{expect_arity, expect_string};
+use object::object::Object;
+use std::rc::Rc;
+
+pub fn builtin_method_len(params: Vec<Rc<Object>>) -> Rc<Object> {
+    let receiver = match expect_arity("len", &params, 0).and(expect_string("len", &params, 0)) {
+        Ok(receiver) => receiver,
+        Err(e) => return e,
+    };
+
+    Rc::from(Object::Integer(receiver.chars().count() as i64))
+}
+
+pub fn builtin_method_upper(params: Vec<Rc<Object>>) -> Rc<Object> {
+    match expect_arity("upper", &params, 0).and(expect_string("upper", &params, 0)) {
+        Ok(receiver) => Rc::from(Object::String(receiver.to_uppercase())),
+        Err(e) => e,
+    }
+}
+
+pub fn builtin_method_lower(params: Vec<Rc<Object>>) -> Rc<Object> {
+    match expect_arity("lower", &params, 0).and(expect_string("lower", &params, 0)) {
+        Ok(receiver) => Rc::from(Object::String(receiver.to_lowercase())),
+        Err(e) => e,
+    }
+}
+
+pub fn builtin_method_trim(params: Vec<Rc<Object>>) -> Rc<Object> {
+    match expect_arity("trim", &params, 0).and(expect_string("trim", &params, 0)) {
+        Ok(receiver) => Rc::from(Object::String(receiver.trim().to_string())),
+        Err(e) => e,
+    }
+}
+
+pub fn builtin_method_contains(params: Vec<Rc<Object>>) -> Rc<Object> {
+    let (receiver, pattern) = match string_and_pattern("contains", &params) {
+        Ok(args) => args,
+        Err(e) => return e,
+    };
+
+    Rc::from(Object::Boolean(receiver.contains(pattern)))
+}
+
+pub fn builtin_method_starts_with(params: Vec<Rc<Object>>) -> Rc<Object> {
+    let (receiver, pattern) = match string_and_pattern("starts_with", &params) {
+        Ok(args) => args,
+        Err(e) => return e,
+    };
+
+    Rc::from(Object::Boolean(receiver.starts_with(pattern)))
+}
+
+pub fn builtin_method_ends_with(params: Vec<Rc<Object>>) -> Rc<Object> {
+    let (receiver, pattern) = match string_and_pattern("ends_with", &params) {
+        Ok(args) => args,
+        Err(e) => return e,
+    };
+
+    Rc::from(Object::Boolean(receiver.ends_with(pattern)))
+}
+
+pub fn builtin_method_split(params: Vec<Rc<Object>>) -> Rc<Object> {
+    let (receiver, separator) = match string_and_pattern("split", &params) {
+        Ok(args) => args,
+        Err(e) => return e,
+    };
+
+    Rc::from(Object::array(
+        receiver
+            .split(separator)
+            .map(|part| Rc::from(Object::String(part.to_string())))
+            .collect(),
+    ))
+}
+
+pub fn builtin_method_replace(params: Vec<Rc<Object>>) -> Rc<Object> {
+    if let Err(e) = expect_arity("replace", &params, 2) {
+        return e;
+    }
+
+    match (
+        expect_string("replace", &params, 0),
+        expect_string("replace", &params, 1),
+        expect_string("replace", &params, 2),
+    ) {
+        (Ok(receiver), Ok(from), Ok(to)) => Rc::from(Object::String(receiver.replace(from, to))),
+        (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => e,
+    }
+}
+
+fn string_and_pattern<'a>(
+    name: &str,
+    params: &'a [Rc<Object>],
+) -> Result<(&'a str, &'a str), Rc<Object>> {
+    expect_arity(name, params, 1)?;
+
+    Ok((
+        expect_string(name, params, 0)?,
+        expect_string(name, params, 1)?,
+    ))
+}
@@ -0,0 +1,23 @@
+use object::object::Object;
+use std::rc::Rc;
+
+// Only the instances of the structs that have an impl of the trait implement it.
+pub fn builtin_func_implements(params: Vec<Rc<Object>>) -> Rc<Object> {
+    let [value, trait_type] = params.as_slice() else {
+        return Rc::from(Object::Error(format!(
+            "wrong number of arguments for implements! wanted 2 got {}",
+            params.len()
+        )));
+    };
+
+    match (&**value, &**trait_type) {
+        (Object::Instance(instance), Object::Trait(trait_type)) => {
+            Rc::from(Object::Boolean(instance.ty.implements(trait_type)))
+        }
+        (_, Object::Trait(_)) => Rc::from(Object::Boolean(false)),
+        (_, obj) => Rc::from(Object::Error(format!(
+            "expected a trait as argument 1 of implements but got {}",
+            obj.type_name()
+        ))),
+    }
+}
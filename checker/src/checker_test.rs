@@ -0,0 +1,59 @@
+#[cfg(test)]
+mod tests {
+    use ast::Node;
+    use parser::Parser;
+
+    use crate::check;
+
+    fn assert_check(input: &str, expected_errors: &[&str]) {
+        match Parser::parse(input.to_string()) {
+            Ok(Node::Program(program)) => match check(&program) {
+                Ok(_) => assert!(expected_errors.is_empty()),
+                Err(errors) => assert_eq!(errors, expected_errors),
+            },
+            Ok(node) => panic!("expected a program but got {}", node),
+            Err(e) => panic!("parse error: {}", e[0]),
+        }
+    }
+
+    #[test]
+    fn test_valid_constants() {
+        assert_check(
+            "const PI = 3; #radius = 2; #area = PI * radius * radius;",
+            &[],
+        );
+        assert_check("const LIMIT = 10; fn foo(LIMIT) { ret LIMIT; }", &[]);
+        assert_check("const LIMIT = 10; if (true) { const LIMIT = 20; }", &[]);
+    }
+
+    #[test]
+    fn test_redeclaring_constant() {
+        assert_check(
+            "const LIMIT = 10; #LIMIT = 20;",
+            &["redeclaring constant LIMIT is not allowed"],
+        );
+        assert_check(
+            "const LIMIT = 10; const LIMIT = 20;",
+            &["redeclaring constant LIMIT is not allowed"],
+        );
+        assert_check(
+            "#limit = 10; const limit = 20;",
+            &["limit is already declared and can not be redeclared as a constant"],
+        );
+    }
+
+    #[test]
+    fn test_assigning_to_constant() {
+        assert_check(
+            "const LIMIT = 10; LIMIT = 20; LIMIT++;",
+            &[
+                "assigning to constant LIMIT is not allowed",
+                "assigning to constant LIMIT is not allowed",
+            ],
+        );
+        assert_check(
+            "const LIMIT = 10; fn foo() { LIMIT = 20; }",
+            &["assigning to constant LIMIT is not allowed"],
+        );
+    }
+}
@@ -0,0 +1,329 @@
+use ast::{
+    expression::{
+        Array, Assignment, BinaryExpression, Comprehension, Conditional, Expression, Field,
+        FunctionCall, Hash, HashEntry, Identifier, Index, MethodCall, Set, Spread, Tuple,
+        UnaryExpression,
+    },
+    program::Program,
+    statement::{
+        BlockStatement, Defer, For, ForIn, Function, If, Impl, Return, Statement, Struct, Trait,
+        TupleDeclaration, Variable, Yield,
+    },
+};
+use std::collections::HashMap;
+use token::TokenKind;
+
+mod checker_test;
+
+pub type CheckError = String;
+
+// The checker walks the program without evaluating it and reports the mistakes
+// that can be detected statically, such as redeclaring or assigning to a constant.
+pub fn check(program: &Program) -> Result<(), Vec<CheckError>> {
+    let mut checker = Checker::new();
+    checker.check_statements(&program.body);
+
+    if !checker.errors.is_empty() {
+        return Err(checker.errors);
+    }
+
+    Ok(())
+}
+
+struct Checker {
+    // every scope maps the declared names to whether they are constant or not
+    scopes: Vec<HashMap<String, bool>>,
+    errors: Vec<CheckError>,
+}
+
+impl Checker {
+    fn new() -> Self {
+        Self {
+            scopes: vec![HashMap::new()],
+            errors: vec![],
+        }
+    }
+
+    fn enter_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    fn leave_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    fn current_scope(&mut self) -> &mut HashMap<String, bool> {
+        self.scopes
+            .last_mut()
+            .expect("checker must always have a global scope")
+    }
+
+    fn is_const(&self, name: &str) -> bool {
+        for scope in self.scopes.iter().rev() {
+            if let Some(is_const) = scope.get(name) {
+                return *is_const;
+            }
+        }
+
+        false
+    }
+
+    fn declare(&mut self, name: &str, is_const: bool) {
+        match self.current_scope().get(name) {
+            Some(true) => {
+                self.errors
+                    .push(format!("redeclaring constant {} is not allowed", name));
+            }
+            Some(false) if is_const => {
+                self.errors.push(format!(
+                    "{} is already declared and can not be redeclared as a constant",
+                    name
+                ));
+            }
+            _ => {
+                self.current_scope().insert(name.to_string(), is_const);
+            }
+        }
+    }
+
+    fn check_assignment_target(&mut self, name: &str) {
+        if self.is_const(name) {
+            self.errors
+                .push(format!("assigning to constant {} is not allowed", name));
+        }
+    }
+
+    fn check_statements(&mut self, statements: &[Statement]) {
+        for statement in statements {
+            self.check_statement(statement);
+        }
+    }
+
+    fn check_block(&mut self, block: &BlockStatement) {
+        self.enter_scope();
+        self.check_statements(&block.body);
+        self.leave_scope();
+    }
+
+    fn check_statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::VariableDeclaration(variable) => self.check_variable(variable, false),
+            Statement::ConstantDeclaration(constant) => self.check_variable(constant, true),
+            Statement::TupleDeclaration(TupleDeclaration {
+                names,
+                expr,
+                constant,
+                ..
+            }) => {
+                self.check_expression(expr);
+
+                for name in names {
+                    self.declare(&name.name, *constant);
+                }
+            }
+            Statement::Expression(expr) => self.check_expression(expr),
+            Statement::Block(block) => self.check_block(block),
+            Statement::Return(Return { argument, .. }) => self.check_expression(argument),
+            Statement::Yield(Yield { argument, .. }) => self.check_expression(argument),
+            Statement::Defer(Defer { argument, .. }) => self.check_expression(argument),
+            // the parser already checked that the labels belong to the enclosing loops
+            Statement::Break(_) | Statement::Continue(_) => {}
+            Statement::ForIn(ForIn {
+                variable,
+                iterable,
+                body,
+                ..
+            }) => {
+                self.check_expression(iterable);
+
+                self.enter_scope();
+                self.declare(&variable.name, false);
+                self.check_block(body);
+                self.leave_scope();
+            }
+            Statement::Function(function) | Statement::Macro(function) => {
+                self.declare(&function.name, false);
+                self.check_function(function);
+            }
+            Statement::Struct(Struct { name, .. }) => self.declare(&name.name, false),
+            Statement::Impl(Impl { methods, .. }) => {
+                for method in methods {
+                    self.check_function(method);
+                }
+            }
+            Statement::Trait(Trait { name, methods, .. }) => {
+                self.declare(&name.name, false);
+
+                for method in methods {
+                    self.check_function(method);
+                }
+            }
+            Statement::If(if_stmt) => self.check_if(if_stmt),
+            Statement::For(For {
+                initializer,
+                condition,
+                increment,
+                body,
+                ..
+            }) => {
+                self.enter_scope();
+
+                if let Some(variable) = initializer {
+                    self.check_variable(variable, false);
+                }
+
+                if let Some(condition) = condition {
+                    self.check_expression(condition);
+                }
+
+                if let Some(increment) = increment {
+                    self.check_expression(increment);
+                }
+
+                self.check_block(body);
+                self.leave_scope();
+            }
+        }
+    }
+
+    fn check_function(&mut self, function: &Function) {
+        // parameters share the scope of the function body
+        self.enter_scope();
+        for param in &function.params {
+            self.declare(&param.name, false);
+        }
+        self.check_statements(&function.body.body);
+        self.leave_scope();
+    }
+
+    fn check_if(&mut self, if_stmt: &If) {
+        self.check_expression(&if_stmt.condition);
+        self.check_block(&if_stmt.consequent);
+
+        for branch in &if_stmt.branches {
+            self.check_if(branch);
+        }
+
+        if let Some(alternate) = &if_stmt.alternate {
+            self.check_block(alternate);
+        }
+    }
+
+    fn check_variable(&mut self, variable: &Variable, is_const: bool) {
+        self.check_expression(&variable.expr);
+
+        if let TokenKind::Identifier { name } = &variable.identifier.kind {
+            self.declare(name, is_const);
+        }
+    }
+
+    fn check_expressions(&mut self, exprs: &[Expression]) {
+        for expr in exprs {
+            self.check_expression(expr);
+        }
+    }
+
+    fn check_expression(&mut self, expr: &Expression) {
+        match expr {
+            Expression::Identifier(_) | Expression::Literal(_) => {}
+            Expression::UnaryOperator(unop) => self.check_assignment_target(&unop.identifer.name),
+            Expression::Assignment(Assignment { target, value, .. }) => {
+                self.check_expression(value);
+
+                match &**target {
+                    Expression::Identifier(Identifier { name, .. }) => {
+                        self.check_assignment_target(name)
+                    }
+                    // xs[i] = v and p.x = v change the collection, not the variable
+                    target => self.check_expression(target),
+                }
+            }
+            Expression::Prefix(UnaryExpression { operand, .. }) => self.check_expression(operand),
+            Expression::If(if_expr) => self.check_if(if_expr),
+            Expression::Block(block) => self.check_block(block),
+            Expression::Conditional(Conditional {
+                condition,
+                consequent,
+                alternate,
+                ..
+            }) => {
+                self.check_expression(condition);
+                self.check_expression(consequent);
+                self.check_expression(alternate);
+            }
+            Expression::Infix(BinaryExpression { left, right, .. }) => {
+                self.check_expression(left);
+                self.check_expression(right);
+            }
+            Expression::FunctionCall(FunctionCall {
+                call, arguments, ..
+            }) => {
+                self.check_expression(call);
+                self.check_expressions(arguments);
+            }
+            Expression::MethodCall(MethodCall {
+                receiver,
+                arguments,
+                ..
+            }) => {
+                self.check_expression(receiver);
+                self.check_expressions(arguments);
+            }
+            Expression::Field(Field { receiver, .. }) => self.check_expression(receiver),
+            Expression::Array(Array { elements, .. }) => self.check_expressions(elements),
+            Expression::Tuple(Tuple { elements, .. }) => self.check_expressions(elements),
+            Expression::Set(Set { elements, .. }) => self.check_expressions(elements),
+            Expression::Hash(Hash { entries, .. }) => {
+                for entry in entries {
+                    match entry {
+                        HashEntry::Pair(key, value) => {
+                            self.check_expression(key);
+                            self.check_expression(value);
+                        }
+                        HashEntry::Spread(Spread { argument, .. }) => {
+                            self.check_expression(argument)
+                        }
+                    }
+                }
+            }
+            Expression::Spread(Spread { argument, .. }) => self.check_expression(argument),
+            Expression::Index(Index { left, index, .. }) => {
+                self.check_expression(left);
+                self.check_expression(index);
+            }
+            Expression::Slice(slice) => {
+                self.check_expression(&slice.left);
+
+                for bound in [&slice.start, &slice.end, &slice.step]
+                    .into_iter()
+                    .flatten()
+                {
+                    self.check_expression(bound);
+                }
+            }
+            Expression::ListComprehension(list) => {
+                self.enter_comprehension(&list.comprehension);
+                self.check_expression(&list.element);
+                self.leave_scope();
+            }
+            Expression::HashComprehension(hash) => {
+                self.enter_comprehension(&hash.comprehension);
+                self.check_expression(&hash.key);
+                self.check_expression(&hash.value);
+                self.leave_scope();
+            }
+        }
+    }
+
+    // Enters the scope of a comprehension, where its variable is declared.
+    fn enter_comprehension(&mut self, comprehension: &Comprehension) {
+        self.check_expression(&comprehension.iterable);
+
+        self.enter_scope();
+        self.declare(&comprehension.variable.name, false);
+
+        if let Some(condition) = &comprehension.condition {
+            self.check_expression(condition);
+        }
+    }
+}
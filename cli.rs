@@ -1,4 +1,9 @@
-use evaluator::eval;
+use ast::Node;
+use checker::check;
+use docgen::{render, Format, Module};
+use evaluator::{
+    eval_source, run_with_stack, set_max_call_depth, set_tail_call_optimization, STACK_SIZE,
+};
 use object::{
     env::Env,
     object::{EvalError, Object},
@@ -9,6 +14,7 @@ use std::{
     env, fs,
     io::{self, Write},
     ops::Index,
+    path::{Path, PathBuf},
     rc::Rc,
 };
 
@@ -17,8 +23,37 @@ const VERSION: &str = env!("CARGO_PKG_VERSION");
 static PROMPT: &str = "(taha) > ";
 static RESULT: &str = "=>";
 
+// The evaluator recurses on the native stack, a big one lets deep recursions
+// reach the maximum call depth instead of the stack limit.
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    if run_with_stack(STACK_SIZE, start).is_err() {
+        std::process::exit(1);
+    }
+}
+
+fn start() {
+    let (switches, args): (Vec<String>, Vec<String>) =
+        env::args().partition(|arg| arg.starts_with("--"));
+
+    let mut doc_format = Format::Markdown;
+
+    for switch in &switches {
+        match switch.as_str() {
+            "--no-tail-calls" => set_tail_call_optimization(false),
+            "--html" => doc_format = Format::Html,
+            s if s.starts_with("--max-depth=") => match s["--max-depth=".len()..].parse() {
+                Ok(depth) => set_max_call_depth(depth),
+                Err(_) => {
+                    write_line(&format!("invalid maximum call depth {}", switch));
+                    std::process::exit(1);
+                }
+            },
+            _ => {
+                write_line(&format!("unknown switch {}", switch));
+                std::process::exit(1);
+            }
+        }
+    }
 
     if args.len() > 1 {
         let option = args.index(1).as_str();
@@ -37,15 +72,33 @@ fn main() {
                     Err(err) => {
                         write_line(&err.to_string());
                     }
-                    Ok(file_content) => match run(&file_content) {
-                        Err(e) => {
+                    Ok(file_content) => {
+                        if let Err(e) = run(&file_content) {
                             write_line(e.as_str());
                         }
-                        Ok(_) => {}
-                    },
+                    }
                 }
             }
-            "help" | "h" | _ => {
+            "check" | "c" => {
+                let file_path = args.index(2);
+
+                match fs::read_to_string(file_path) {
+                    Err(err) => {
+                        write_line(&err.to_string());
+                    }
+                    Ok(file_content) => {
+                        if !run_check(&file_content) {
+                            std::process::exit(1);
+                        }
+                    }
+                }
+            }
+            "doc" | "d" => {
+                if !run_doc(Path::new(args.index(2)), doc_format) {
+                    std::process::exit(1);
+                }
+            }
+            _ => {
                 show_help_menu();
             }
         };
@@ -65,6 +118,12 @@ fn show_help_menu() {
     println!("version, v: print the version number");
     println!("help, h: show this message");
     println!("run, r: <file_path>");
+    println!("check, c: <file_path>");
+    println!("doc, d: <file_path|dir_path> render the doc comments as markdown, or html with --html");
+    println!("--no-tail-calls: keep every call on the stack, useful for debugging");
+    println!("--max-depth=<depth>: the maximum depth of the calls, 1000 by default, capped by the stack");
+    println!("    the calls in tail position (ret f(x)) run in a loop and don't count against it, so");
+    println!("    a tail recursion that never ends runs forever unless --no-tail-calls is given");
 }
 
 fn show_version() {
@@ -82,9 +141,10 @@ pub fn start_repl() {
                 let result = run(&buf);
 
                 match result {
-                    Ok(obj) => {
-                        write_line(&obj.to_string());
-                    }
+                    Ok(obj) => match obj.to_text() {
+                        Ok(text) => write_line(&text),
+                        Err(e) => write_line(&e),
+                    },
                     Err(e) => write_line(&e),
                 }
             }
@@ -97,7 +157,84 @@ pub fn start_repl() {
 
 fn run(input: &str) -> Result<Rc<Object>, EvalError> {
     let env: Env = Rc::new(RefCell::new(Default::default()));
-    let node = Parser::parse(input.to_string()).unwrap();
 
-    eval(node, &env)
+    eval_source(input, &env)
+}
+
+fn run_check(input: &str) -> bool {
+    let errors = match Parser::parse(input.to_string()) {
+        Ok(Node::Program(program)) => match check(&program) {
+            Ok(_) => vec![],
+            Err(errors) => errors,
+        },
+        Ok(_) => vec![],
+        Err(errors) => errors,
+    };
+
+    for error in &errors {
+        write_line(error);
+    }
+
+    errors.is_empty()
+}
+
+// Collects the taha files of the directory and its subdirectories.
+fn collect_sources(dir: &Path, sources: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+
+        if path.is_dir() {
+            collect_sources(&path, sources)?;
+        } else if path.extension().is_some_and(|ext| ext == "taha") {
+            sources.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+fn run_doc(path: &Path, format: Format) -> bool {
+    let mut sources = vec![];
+
+    if path.is_dir() {
+        if let Err(err) = collect_sources(path, &mut sources) {
+            write_line(&err.to_string());
+            return false;
+        }
+        sources.sort();
+    } else {
+        sources.push(path.to_path_buf());
+    }
+
+    let mut modules = vec![];
+
+    for source in sources {
+        let name = match source.strip_prefix(path) {
+            Ok(relative) if path.is_dir() => relative.with_extension(""),
+            _ => PathBuf::from(source.file_stem().unwrap_or_default()),
+        };
+
+        let program = match fs::read_to_string(&source).map(Parser::parse) {
+            Ok(Ok(Node::Program(program))) => program,
+            Ok(Ok(_)) => continue,
+            Ok(Err(errors)) => {
+                for error in &errors {
+                    write_line(&format!("{}: {}", source.display(), error));
+                }
+                return false;
+            }
+            Err(err) => {
+                write_line(&format!("{}: {}", source.display(), err));
+                return false;
+            }
+        };
+
+        modules.push(Module {
+            name: name.display().to_string(),
+            program,
+        });
+    }
+
+    print!("{}", render(&modules, format));
+    true
 }
@@ -0,0 +1,87 @@
+#[cfg(test)]
+mod tests {
+    use ast::Node;
+    use parser::Parser;
+
+    use crate::{render, Format, Module};
+
+    fn module(name: &str, input: &str) -> Module {
+        match Parser::parse(input.to_string()) {
+            Ok(Node::Program(program)) => Module {
+                name: name.to_string(),
+                program,
+            },
+            Ok(node) => panic!("expected a program but got {}", node),
+            Err(e) => panic!("parse error: {}", e[0]),
+        }
+    }
+
+    const SOURCE: &str = "
+        /// A point on the plane.
+        struct Point { x, y }
+
+        impl Point {
+            /// Moves the point, see `add`.
+            fn shift(self, dx) { Point(self.x + dx, self.y) }
+        }
+
+        /// Adds two numbers.
+        ///
+        /// Works with `Point` too.
+        fn add(a, b) { a + b }
+
+        fn undocumented() {}
+
+        /// Something with an area.
+        trait Shape {
+            /// The area of the shape.
+            fn area(self);
+            fn double_area(self) { self.area() * 2 }
+        }
+
+        /// Runs `body` when the condition is false.
+        macro unless(condition, body) { quote(if !(unquote(condition)) { unquote(body) }) }
+    ";
+
+    #[test]
+    fn test_render_markdown() {
+        let out = render(&[module("math", SOURCE)], Format::Markdown);
+
+        assert!(out.contains("- [math](#math)"));
+        assert!(out.contains(
+            "<a id=\"math-Point\"></a>\n\n### `struct Point { x, y }`\n\nA point on the plane."
+        ));
+        assert!(
+            out.contains("#### `fn shift(self, dx)`\n\nMoves the point, see [`add`](#math-add).")
+        );
+        assert!(out.contains(
+            "### `fn add(a, b)`\n\nAdds two numbers.\n\nWorks with [`Point`](#math-Point) too."
+        ));
+        assert!(out.contains("### `fn undocumented()`"));
+        assert!(out
+            .contains("<a id=\"math-Shape\"></a>\n\n### `trait Shape`\n\nSomething with an area."));
+        assert!(out.contains("#### `fn area(self)`\n\nThe area of the shape."));
+        assert!(out.contains("### `macro unless(condition, body)`\n\nRuns `body` when"));
+        assert!(
+            out.contains("<a id=\"math-Shape-double-area\"></a>\n\n#### `fn double_area(self)`")
+        );
+    }
+
+    #[test]
+    fn test_render_html() {
+        let out = render(
+            &[
+                module("math", SOURCE),
+                module("main", "/// Uses `add` & `missing`.\nfn main() {}"),
+            ],
+            Format::Html,
+        );
+
+        assert!(out.contains("<h2 id=\"main\">main</h2>"));
+        assert!(out.contains("<h3 id=\"main-main\"><code>fn main()</code></h3>"));
+        assert!(out.contains(
+            "<p>Uses <a href=\"#math-add\"><code>add</code></a> &amp; <code>missing</code>.</p>"
+        ));
+        assert!(out.contains("<p>Adds two numbers.</p>\n<p>Works with"));
+    }
+}
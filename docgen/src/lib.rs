@@ -0,0 +1,353 @@
+use ast::{
+    expression::Identifier,
+    program::Program,
+    statement::{Function, Impl, Statement, Struct, Trait},
+};
+use std::collections::HashMap;
+
+mod docgen_test;
+
+pub enum Format {
+    Markdown,
+    Html,
+}
+
+// A source file, its declarations are documented under its name.
+pub struct Module {
+    pub name: String,
+    pub program: Program,
+}
+
+struct Page {
+    name: String,
+    anchor: String,
+    items: Vec<Item>,
+}
+
+struct Item {
+    name: String,
+    anchor: String,
+    signature: String,
+    doc: Option<String>,
+    methods: Vec<Item>,
+}
+
+// Renders the signatures and the doc comments of the functions and structs of the
+// modules into a single static page. A name written in backticks in a doc comment
+// links to the declaration with that name.
+pub fn render(modules: &[Module], format: Format) -> String {
+    let pages: Vec<Page> = modules.iter().map(collect_page).collect();
+    let links = collect_links(&pages);
+
+    match format {
+        Format::Markdown => render_markdown(&pages, &links),
+        Format::Html => render_html(&pages, &links),
+    }
+}
+
+fn anchor(parts: &[&str]) -> String {
+    parts
+        .join("-")
+        .chars()
+        .map(|ch| if ch.is_alphanumeric() { ch } else { '-' })
+        .collect()
+}
+
+fn collect_page(module: &Module) -> Page {
+    let page_anchor = anchor(&[&module.name]);
+    let mut items: Vec<Item> = Vec::new();
+    let mut impls: Vec<&Impl> = Vec::new();
+
+    for statement in &module.program.body {
+        match statement {
+            Statement::Function(function) => {
+                items.push(function_item(function, &function.name, &page_anchor));
+            }
+            Statement::Struct(struct_stmt) => items.push(struct_item(struct_stmt, &page_anchor)),
+            Statement::Impl(impl_stmt) => impls.push(impl_stmt),
+            Statement::Trait(trait_stmt) => items.push(trait_item(trait_stmt, &page_anchor)),
+            Statement::Macro(definition) => {
+                let mut item = function_item(definition, &definition.name, &page_anchor);
+                item.signature = format!("macro {}", item.signature.trim_start_matches("fn "));
+                items.push(item);
+            }
+            _ => {}
+        }
+    }
+
+    // the methods are listed under their struct, or under the impl itself when
+    // the struct is declared in another module
+    for impl_stmt in impls {
+        let type_name = &impl_stmt.name.name;
+        let methods = impl_stmt.methods.iter().map(|method| {
+            function_item(
+                method,
+                &format!("{}.{}", type_name, method.name),
+                &page_anchor,
+            )
+        });
+
+        let signature = match &impl_stmt.trait_name {
+            Some(trait_name) => format!("impl {} for {}", trait_name, type_name),
+            None => format!("impl {}", type_name),
+        };
+
+        match items.iter_mut().find(|item| &item.name == type_name) {
+            Some(item) => item.methods.extend(methods),
+            None => items.push(Item {
+                name: format!("impl {}", type_name),
+                anchor: anchor(&[&page_anchor, "impl", type_name]),
+                signature,
+                doc: None,
+                methods: methods.collect(),
+            }),
+        }
+    }
+
+    Page {
+        name: module.name.clone(),
+        anchor: page_anchor,
+        items,
+    }
+}
+
+fn function_item(function: &Function, name: &str, page_anchor: &str) -> Item {
+    signature_item(
+        &function.name,
+        &function.params,
+        &function.doc,
+        name,
+        page_anchor,
+    )
+}
+
+fn signature_item(
+    function_name: &str,
+    params: &[Identifier],
+    doc: &Option<String>,
+    name: &str,
+    page_anchor: &str,
+) -> Item {
+    let params: Vec<&str> = params.iter().map(|param| param.name.as_str()).collect();
+
+    Item {
+        name: name.to_string(),
+        anchor: anchor(&[page_anchor, name]),
+        signature: format!("fn {}({})", function_name, params.join(", ")),
+        doc: doc.clone(),
+        methods: vec![],
+    }
+}
+
+// The required methods of a trait are listed before its default methods.
+fn trait_item(trait_stmt: &Trait, page_anchor: &str) -> Item {
+    let trait_name = &trait_stmt.name.name;
+
+    let required = trait_stmt.required.iter().map(|signature| {
+        signature_item(
+            &signature.name,
+            &signature.params,
+            &signature.doc,
+            &format!("{}.{}", trait_name, signature.name),
+            page_anchor,
+        )
+    });
+
+    let defaults = trait_stmt.methods.iter().map(|method| {
+        function_item(
+            method,
+            &format!("{}.{}", trait_name, method.name),
+            page_anchor,
+        )
+    });
+
+    Item {
+        name: trait_name.clone(),
+        anchor: anchor(&[page_anchor, trait_name]),
+        signature: format!("trait {}", trait_name),
+        doc: trait_stmt.doc.clone(),
+        methods: required.chain(defaults).collect(),
+    }
+}
+
+fn struct_item(struct_stmt: &Struct, page_anchor: &str) -> Item {
+    let fields: Vec<&str> = struct_stmt
+        .fields
+        .iter()
+        .map(|field| field.name.as_str())
+        .collect();
+
+    Item {
+        name: struct_stmt.name.name.clone(),
+        anchor: anchor(&[page_anchor, &struct_stmt.name.name]),
+        signature: format!("struct {} {{ {} }}", struct_stmt.name, fields.join(", ")),
+        doc: struct_stmt.doc.clone(),
+        methods: vec![],
+    }
+}
+
+// Maps the names to their anchors, the first declaration wins when a name is
+// declared in more than one module.
+fn collect_links(pages: &[Page]) -> HashMap<String, String> {
+    let mut links = HashMap::new();
+
+    for item in pages.iter().flat_map(|page| &page.items) {
+        for item in std::iter::once(item).chain(&item.methods) {
+            links
+                .entry(item.name.clone())
+                .or_insert_with(|| item.anchor.clone());
+        }
+    }
+
+    links
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// Renders the code spans of a doc comment, linking the ones that name a declaration.
+fn render_inline(text: &str, links: &HashMap<String, String>, format: &Format) -> String {
+    let segments: Vec<&str> = text.split('`').collect();
+    let mut rendered = String::new();
+
+    for (i, segment) in segments.iter().enumerate() {
+        let is_code = i % 2 == 1;
+        let is_closed = i + 1 < segments.len();
+
+        if !is_code {
+            match format {
+                Format::Markdown => rendered.push_str(segment),
+                Format::Html => rendered.push_str(&escape_html(segment)),
+            }
+            continue;
+        }
+
+        if !is_closed {
+            rendered.push('`');
+            rendered.push_str(segment);
+            continue;
+        }
+
+        let link = links.get(segment.trim_end_matches("()"));
+
+        match (format, link) {
+            (Format::Markdown, Some(anchor)) => {
+                rendered.push_str(&format!("[`{}`](#{})", segment, anchor))
+            }
+            (Format::Markdown, None) => rendered.push_str(&format!("`{}`", segment)),
+            (Format::Html, Some(anchor)) => rendered.push_str(&format!(
+                "<a href=\"#{}\"><code>{}</code></a>",
+                anchor,
+                escape_html(segment)
+            )),
+            (Format::Html, None) => {
+                rendered.push_str(&format!("<code>{}</code>", escape_html(segment)))
+            }
+        }
+    }
+
+    rendered
+}
+
+fn render_markdown(pages: &[Page], links: &HashMap<String, String>) -> String {
+    let mut out = String::from("# Documentation\n\n");
+
+    for page in pages {
+        out.push_str(&format!("- [{}](#{})\n", page.name, page.anchor));
+    }
+
+    for page in pages {
+        out.push_str(&format!(
+            "\n<a id=\"{}\"></a>\n\n## {}\n",
+            page.anchor, page.name
+        ));
+
+        for item in &page.items {
+            render_markdown_item(&mut out, item, "###", links);
+
+            for method in &item.methods {
+                render_markdown_item(&mut out, method, "####", links);
+            }
+        }
+    }
+
+    out
+}
+
+fn render_markdown_item(
+    out: &mut String,
+    item: &Item,
+    heading: &str,
+    links: &HashMap<String, String>,
+) {
+    out.push_str(&format!(
+        "\n<a id=\"{}\"></a>\n\n{} `{}`\n",
+        item.anchor, heading, item.signature
+    ));
+
+    if let Some(doc) = &item.doc {
+        out.push_str(&format!(
+            "\n{}\n",
+            render_inline(doc, links, &Format::Markdown)
+        ));
+    }
+}
+
+fn render_html(pages: &[Page], links: &HashMap<String, String>) -> String {
+    let mut out = String::from(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Documentation</title>\n</head>\n<body>\n<h1>Documentation</h1>\n<ul>\n",
+    );
+
+    for page in pages {
+        out.push_str(&format!(
+            "<li><a href=\"#{}\">{}</a></li>\n",
+            page.anchor,
+            escape_html(&page.name)
+        ));
+    }
+
+    out.push_str("</ul>\n");
+
+    for page in pages {
+        out.push_str(&format!(
+            "<h2 id=\"{}\">{}</h2>\n",
+            page.anchor,
+            escape_html(&page.name)
+        ));
+
+        for item in &page.items {
+            render_html_item(&mut out, item, "h3", links);
+
+            for method in &item.methods {
+                render_html_item(&mut out, method, "h4", links);
+            }
+        }
+    }
+
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+fn render_html_item(out: &mut String, item: &Item, heading: &str, links: &HashMap<String, String>) {
+    out.push_str(&format!(
+        "<{} id=\"{}\"><code>{}</code></{}>\n",
+        heading,
+        item.anchor,
+        escape_html(&item.signature),
+        heading
+    ));
+
+    if let Some(doc) = &item.doc {
+        // paragraphs are separated by blank lines like in markdown
+        for paragraph in doc.split("\n\n") {
+            out.push_str(&format!(
+                "<p>{}</p>\n",
+                render_inline(paragraph.trim(), links, &Format::Html)
+            ));
+        }
+    }
+}
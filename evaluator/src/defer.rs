@@ -0,0 +1,63 @@
+use crate::eval_expression;
+use ast::expression::Expression;
+use object::{
+    env::Env,
+    generator::Deferred,
+    object::{EvalError, Object},
+};
+use std::{cell::RefCell, rc::Rc};
+
+thread_local! {
+    // Every running function has a frame with the actions deferred by its body.
+    static FRAMES: RefCell<Vec<Vec<Deferred>>> = const { RefCell::new(vec![]) };
+}
+
+pub fn push_frame(actions: Vec<Deferred>) {
+    FRAMES.with(|frames| frames.borrow_mut().push(actions));
+}
+
+pub fn pop_frame() -> Vec<Deferred> {
+    FRAMES.with(|frames| frames.borrow_mut().pop().unwrap_or_default())
+}
+
+pub fn register(action: Expression, env: &Env) -> Result<Rc<Object>, EvalError> {
+    FRAMES.with(|frames| match frames.borrow_mut().last_mut() {
+        Some(frame) => {
+            frame.push(Deferred {
+                action,
+                env: Rc::clone(env),
+            });
+
+            Ok(Rc::new(Object::Null))
+        }
+        None => Err("defer can only be used inside of a function".to_string()),
+    })
+}
+
+// A tail call would return from the function before the callee runs, so it is
+// not used while the function still has deferred actions.
+pub fn has_pending() -> bool {
+    FRAMES.with(|frames| {
+        frames
+            .borrow()
+            .last()
+            .is_some_and(|frame| !frame.is_empty())
+    })
+}
+
+// Runs the actions of a frame from the last one registered to the first one. An
+// error raised by the body is kept over the errors of the actions.
+pub fn run_frame<T>(actions: Vec<Deferred>, result: Result<T, EvalError>) -> Result<T, EvalError> {
+    let mut failure = None;
+
+    for Deferred { action, env } in actions.into_iter().rev() {
+        if let Err(e) = eval_expression(action, &env) {
+            failure.get_or_insert(e);
+        }
+    }
+
+    match (result, failure) {
+        (Ok(_), Some(e)) => Err(e),
+        (result, _) => result,
+    }
+}
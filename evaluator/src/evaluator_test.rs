@@ -5,14 +5,20 @@ mod tests {
 
     use object::env::Env;
 
-    use crate::eval;
+    use crate::{
+        eval, expand_macros, run_with_stack, set_max_call_depth, set_tail_call_optimization,
+        STACK_SIZE,
+    };
 
     fn assert_eval(test_cases: &[(&str, &str)]) {
         let env: Env = Rc::new(RefCell::new(Default::default()));
         for (input, expected) in test_cases {
             match Parser::parse(input.to_string()) {
-                Ok(node) => match eval(node, &env) {
-                    Ok(evaluated) => assert_eq!(&format!("{}", evaluated), expected),
+                Ok(node) => match expand_macros(node, &env)
+                    .and_then(|node| eval(node, &env))
+                    .and_then(|evaluated| evaluated.to_text())
+                {
+                    Ok(text) => assert_eq!(&text, expected),
                     Err(e) => assert_eq!(&e.to_string(), expected),
                 },
                 Err(e) => panic!("parse error: {}", e[0]),
@@ -27,15 +33,986 @@ mod tests {
             ("(1 + 1) + 1", "3"),
             ("10 / 2 + 1", "6"),
             ("5 + (1 + 1) - 10", "-3"),
+            ("0xFF + 0o10 + 0b1 + 1_000", "1264"),
             // ("10 % 2", "0")
         ]);
     }
 
+    #[test]
+    fn test_string_expressions() {
+        assert_eval(&[
+            ("\"foo\" + \"bar\"", "foobar"),
+            ("\"foo\" == \"foo\"", "true"),
+            ("\"foo\" == \"bar\"", "false"),
+            ("\"foo\" != \"bar\"", "true"),
+        ]);
+    }
+
+    #[test]
+    fn test_big_integer_expressions() {
+        assert_eval(&[
+            ("9223372036854775807 + 1", "9223372036854775808"),
+            ("-9223372036854775808", "-9223372036854775808"),
+            ("-9223372036854775808 - 1", "-9223372036854775809"),
+            ("4294967296 * 4294967296", "18446744073709551616"),
+            ("18446744073709551616 / 4294967296", "4294967296"),
+            ("18446744073709551616 - 18446744073709551615", "1"),
+            ("18446744073709551616 > 9223372036854775807", "true"),
+            ("9223372036854775808 == 9223372036854775807 + 1", "true"),
+            (
+                "\"big: \" + 18446744073709551616",
+                "big: 18446744073709551616",
+            ),
+            ("#n = 9223372036854775807; n++; n", "9223372036854775808"),
+            ("n--; n", "9223372036854775807"),
+            ("1 / 0", "division by zero"),
+        ]);
+    }
 
     #[test]
     fn test_function_declaration() {
+        assert_eval(&[("fn foo_bar(a, b) { ret a + b; }", "null")]);
+    }
+
+    #[test]
+    fn test_constant_declaration() {
+        assert_eval(&[
+            ("const LIMIT = 10; LIMIT", "10"),
+            ("#LIMIT = 20;", "redeclaring constant LIMIT is not allowed"),
+            (
+                "const LIMIT = 20;",
+                "redeclaring constant LIMIT is not allowed",
+            ),
+            ("LIMIT = 20;", "assigning to constant LIMIT is not allowed"),
+            ("LIMIT++", "assigning to constant LIMIT is not allowed"),
+            (
+                "fn foo() { LIMIT = 1; } foo()",
+                "assigning to constant LIMIT is not allowed",
+            ),
+            ("fn bar(LIMIT) { ret LIMIT; } bar(1)", "1"),
+            ("LIMIT", "10"),
+        ]);
+    }
+
+    #[test]
+    fn test_assignment() {
+        assert_eval(&[
+            ("#a = 1; a = a + 1; a", "2"),
+            ("#b = 0; a = b = 5; a + b", "10"),
+            ("fn set_a() { a = 7; } set_a(); a", "7"),
+            ("undeclared = 1", "variable undeclared is not declared"),
+        ]);
+    }
+
+    #[test]
+    fn test_block_scoping() {
+        assert_eval(&[
+            ("#x = 1; if (true) { #tmp = 2; x = x + tmp; } x", "3"),
+            ("tmp", "unknown identifier tmp"),
+            (
+                "for #i = 0; i < 3; i++ { #inner = i; } i",
+                "unknown identifier i",
+            ),
+            ("inner", "unknown identifier inner"),
+            ("{ #x = 10; x }", "10"),
+            ("x", "3"),
+            ("const C = 1; { const C = 2; } C", "1"),
+        ]);
+    }
+
+    #[test]
+    fn test_early_return() {
+        assert_eval(&[
+            (
+                "fn sign(n) { if (n < 0) { ret 0 - 1; } ret 1; } sign(0 - 5)",
+                "-1",
+            ),
+            (
+                "fn first(n) { for #i = 0; i < n; i++ { ret i; } ret n; } first(3)",
+                "0",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_if_expression() {
+        assert_eval(&[
+            ("#x = if 1 < 2 { 10 } else { 20 }; x", "10"),
+            ("#y = if false { 1 } else if true { 2 } else { 3 }; y", "2"),
+            ("#z = if false { 1 }; z", "null"),
+            ("if (x == 10) { println(x); }\nx + 1", "11"),
+            ("#w = { #a = 2; a * 3 }; w", "6"),
+            (
+                "fn abs(n) { #v = if n < 0 { ret -n } else { n }; v } abs(-4)",
+                "4",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_implicit_return() {
+        assert_eval(&[
+            ("fn add(a, b) { a + b } add(1, 2)", "3"),
+            ("fn max(a, b) { if a > b { a } else { b } } max(3, 7)", "7"),
+            ("fn nothing() { #a = 1; } nothing()", "null"),
+        ]);
+    }
+
+    #[test]
+    fn test_conditional_expression() {
+        assert_eval(&[
+            ("true ? 1 : 2", "1"),
+            ("1 > 2 ? 1 : 2", "2"),
+            (
+                "#n = 0; n < 0 ? \"negative\" : n == 0 ? \"zero\" : \"positive\"",
+                "zero",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_arrays() {
+        assert_eval(&[
+            ("[1, 2 + 3, \"a\"]", "[1, 5, a]"),
+            ("#xs = [10, 20, 30]; xs[1]", "20"),
+            ("xs[3]", "index 3 is out of range for array of length 3"),
+            ("\"hello\"[1]", "e"),
+            ("1[0]", "index operator is not supported for type integer"),
+        ]);
+    }
+
+    #[test]
+    fn test_slices() {
+        assert_eval(&[
+            ("#xs = [1, 2, 3, 4, 5]; xs[1:4]", "[2, 3, 4]"),
+            ("xs[:-1]", "[1, 2, 3, 4]"),
+            ("xs[-2:]", "[4, 5]"),
+            ("xs[::2]", "[1, 3, 5]"),
+            ("xs[::-1]", "[5, 4, 3, 2, 1]"),
+            ("xs[3:0:-2]", "[4, 2]"),
+            ("xs[10:]", "[]"),
+            ("xs[-10:2]", "[1, 2]"),
+            ("xs[:99999999999999999999]", "[1, 2, 3, 4, 5]"),
+            ("xs[-99999999999999999999:2]", "[1, 2]"),
+            ("xs[::99999999999999999999]", "[1]"),
+            ("xs[::-99999999999999999999]", "[5]"),
+            ("\"hello world\"[1:4]", "ell"),
+            ("\"سلام 👋\"[-1:]", "👋"),
+            ("\"abc\"[::-1]", "cba"),
+            ("xs[::0]", "slice step can not be zero"),
+            (
+                "xs[\"a\":]",
+                "slice indices must be integers but got string",
+            ),
+            ("1[1:]", "slice operator is not supported for type integer"),
+        ]);
+    }
+
+    #[test]
+    fn test_sets() {
+        assert_eval(&[
+            ("#{3, 1, 2, 1}", "#{1, 2, 3}"),
+            ("#{}", "#{}"),
+            (
+                "#words = [\"b\", \"a\", \"b\"]; #seen = #{...words}; seen",
+                "#{a, b}",
+            ),
+            ("\"a\" in seen", "true"),
+            ("\"z\" in seen", "false"),
+            ("#{1, 2} | #{2, 3}", "#{1, 2, 3}"),
+            ("#{1, 2} & #{2, 3}", "#{2}"),
+            ("#{1, 2} - #{2, 3}", "#{1}"),
+            ("#{1, 2} == #{2, 1}", "true"),
+            ("#{1} <= #{1, 2}", "true"),
+            ("seen.add(\"c\").len()", "3"),
+            ("seen.remove(\"a\")", "#{b, c}"),
+            ("seen.union(#{1}).intersection(#{1, \"b\"})", "#{1, b}"),
+            ("#{1, 2}.difference(#{1})", "#{2}"),
+            ("[x for x in [1, 2, 3, 4] if x in #{2, 4}]", "[2, 4]"),
+            ("2 in [1, 2]", "true"),
+            ("1 in [1.0d]", "true"),
+            ("1.0d in (2, 1)", "true"),
+            ("[1] in [[1]]", "true"),
+            ("#me = []; me.push(me); me in me", "true"),
+            ("3 in [1, \"a\", [2]]", "false"),
+            ("[(1, [2])] in [0, [(1, [2])]]", "true"),
+            (
+                "struct Id { n } impl Id { fn eq(self, o) { self.n == o.n } } Id(1) in [Id(2), Id(1)]",
+                "true",
+            ),
+            ("struct P { x } #p = P(1); [p in [p], P(1) in [p]]", "[true, false]"),
+            (
+                "struct Bad { n } impl Bad { fn eq(self, o) { 1 / 0 } } Bad(1) in [Bad(1)]",
+                "division by zero",
+            ),
+            ("\"k\" in {\"k\": 1}", "true"),
+            ("\"ell\" in \"hello\"", "true"),
+            ("#{[1]}", "type array can not be an element of a set"),
+            ("1 in 2", "in operator is not supported for type integer"),
+            ("#{1} | [1]", "eval infix not available for operator: |"),
+            (
+                "#{1}.union([1])",
+                "expected a set as argument 1 of union but got array",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_decimals() {
+        assert_eval(&[
+            ("12.34d", "12.34"),
+            ("0.1d + 0.2d == 0.3d", "true"),
+            ("19.99d * 3", "59.97"),
+            ("10.00d - 0.5d", "9.50"),
+            ("-1.25d", "-1.25"),
+            ("10.00d / 4", "2.50"),
+            ("1d / 3", "0.3333333333333333333333333333"),
+            ("1.5d == 1.50d", "true"),
+            ("100 == 100.00d", "true"),
+            ("2 < 2.01d", "true"),
+            ("\"total: \" + 9.90d", "total: 9.90"),
+            ("decimal(\"12.30\") + 1", "13.30"),
+            ("decimal(5)", "5"),
+            ("2.665d.round(2)", "2.66"),
+            ("2.665d.round(2, \"half_up\")", "2.67"),
+            ("(-2.5d).round(0, \"floor\")", "-3"),
+            ("1.5d.round(3)", "1.500"),
+            ("100.00d.div(3, 2)", "33.33"),
+            ("100d.div(3, 2, \"up\")", "33.34"),
+            ("(-100d).div(3, 2, \"ceiling\")", "-33.33"),
+            ("1d / 0", "division by zero"),
+            ("decimal(\"1.2.3\")", "invalid decimal 1.2.3"),
+            (
+                "1d.round(2, \"nearest\")",
+                "unknown rounding mode nearest, expected half_even, half_up, half_down, up, down, ceiling or floor",
+            ),
+            ("1d.round(-1)", "the places of round can not be negative"),
+            (
+                "1.5d.round(100000000)",
+                "the places of round can not be more than 1000",
+            ),
+            (
+                "1d.div(3, 99999999999999999999)",
+                "the places of div can not be more than 1000",
+            ),
+            ("1d.div(8, 1000).round(3)", "0.125"),
+            (
+                "1d.round(-99999999999999999999)",
+                "the places of round can not be negative",
+            ),
+            ("true + 1d", "eval infix not available for operator: +"),
+        ]);
+    }
+
+    #[test]
+    fn test_bytes() {
+        let path = std::env::temp_dir().join("taha_evaluator_test_bytes.bin");
+        let path = path.to_string_lossy().replace('\\', "/");
+
+        assert_eval(&[
+            ("#data = b\"ab\\x00\\xff\"; data", "b\"ab\\x00\\xff\""),
+            ("data.len()", "4"),
+            ("data[3]", "255"),
+            ("data[1:3]", "b\"b\\x00\""),
+            ("[...data]", "[97, 98, 0, 255]"),
+            ("data.hex()", "616200ff"),
+            ("data.base64()", "YWIA/w=="),
+            ("data + b\"!\" == b\"ab\\x00\\xff!\"", "true"),
+            ("255 in data", "true"),
+            ("\"héllo\".encode(\"utf-8\")", "b\"h\\xc3\\xa9llo\""),
+            ("\"héllo\".encode(\"latin-1\").decode(\"latin-1\")", "héllo"),
+            (
+                "\"é\".encode(\"ascii\")",
+                "character é can not be encoded as ascii",
+            ),
+            (
+                "data.decode(\"utf-8\")",
+                "bytes are not valid utf-8: invalid utf-8 sequence of 1 bytes from index 3",
+            ),
+            (
+                "data.decode(\"utf-16\")",
+                "unknown encoding utf-16, expected utf-8, ascii or latin-1",
+            ),
+            (&format!("write_bytes(\"{}\", data)", path), "null"),
+            (&format!("read_bytes(\"{}\") == data", path), "true"),
+            ("data[4]", "index 4 is out of range for bytes of length 4"),
+        ]);
+    }
+
+    #[test]
+    fn test_method_calls() {
+        assert_eval(&[
+            ("\"abc\".len()", "3"),
+            ("\"  Taha  \".trim().upper()", "TAHA"),
+            ("\"a,b,c\".split(\",\").reverse().join(\"-\")", "c-b-a"),
+            ("#xs = [1, 2]; xs.push(3)", "[1, 2, 3]"),
+            ("xs.len()", "3"),
+            ("(-5).abs()", "5"),
+            ("fn double(n) { n * 2 } 21.double()", "42"),
+            (
+                "fn greet(name, greeting) { greeting + \", \" + name } \"Taha\".greet(\"Hi\")",
+                "Hi, Taha",
+            ),
+            (
+                "\"abc\".missing()",
+                "unknown method missing for type string",
+            ),
+            ("[].missing()", "unknown method missing for type array"),
+            (
+                "\"abc\".len(1)",
+                "wrong number of arguments for len! wanted 0 got 1",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_for_in_statement() {
+        assert_eval(&[
+            ("#sum = 0; for x in [1, 2, 3] { sum = sum + x; } sum", "6"),
+            (
+                "#letters = \"\"; for ch in \"abc\" { letters = ch + letters; } letters",
+                "cba",
+            ),
+            ("x", "unknown identifier x"),
+            ("for x in 10 { }", "type integer is not iterable"),
+        ]);
+    }
+
+    #[test]
+    fn test_break_and_continue() {
+        assert_eval(&[
+            (
+                "#seen = []; for x in [1, 2, 3, 4, 5] { if x == 2 { continue; } if x == 4 { break; } seen.push(x); } seen",
+                "[1, 3]",
+            ),
+            (
+                "#n = 0; for #i = 0; i < 10; i++ { if i < 5 { continue; } n = i; break; } n",
+                "5",
+            ),
+            (
+                "#grid = [[1, 2], [3, 4], [5, 6]]; #found = (); \
+                outer: for #row = 0; row < 3; row++ { for x in grid[row] { if x == 4 { found = (row, x); break outer; } } } found",
+                "(1, 4)",
+            ),
+            (
+                "#pairs = []; outer: for a in [1, 2, 3] { for b in [1, 2, 3] { if b > a { continue outer; } pairs.push((a, b)); } } pairs",
+                "[(1, 1), (2, 1), (2, 2), (3, 1), (3, 2), (3, 3)]",
+            ),
+            (
+                "fn find(xs) { for x in xs { #y = { if x > 1 { break; } x }; } ret \"done\"; } find([1, 2])",
+                "done",
+            ),
+            (
+                "fn evens(n) { outer: for #i = 0; i < n; i++ { for x in [i] { if x == 3 { break outer; } if x == 1 { continue outer; } yield x; } } }
+                #xs = []; for x in evens(10) { xs.push(x); } xs",
+                "[0, 2]",
+            ),
+            (
+                "fn odd() { for x in [1, 2, 3] { if x == 2 { continue; } yield x; } } [...odd()]",
+                "[1, 3]",
+            ),
+            (
+                "#hits = 0; { outer: for x in [1, 2] { for y in [1, 2] { hits++; break outer; } } } hits",
+                "1",
+            ),
+            (
+                "#last = { outer: for x in [1] { continue outer; } 5 }; last",
+                "5",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_generators() {
+        assert_eval(&[
+            (
+                "fn count_to(n) { for #i = 1; i <= n; i++ { yield i; } }
+                #gen = count_to(2);",
+                "null",
+            ),
+            ("gen", "[generator]"),
+            ("gen.next()", "1"),
+            ("gen.next()", "2"),
+            ("gen.next()", "null"),
+            ("gen.next()", "null"),
+            ("#total = 0; for n in count_to(100000) { total = total + n; } total", "5000050000"),
+            (
+                "fn big(xs) { for x in xs { if x > 1 { yield x; } } }
+                fn firsts() { yield 1; { yield 2; } if true { yield 3; } else { yield 4; } ret 5; yield 6; }
+                #seen = []; for x in firsts() { seen = seen.push(x); } seen",
+                "[1, 2, 3]",
+            ),
+            (
+                "fn take(gen, n) { for #i = 0; i < n; i++ { yield gen.next(); } }
+                fn naturals() { #n = 0; for #forever = true; forever; n++ { yield n; } }
+                #firsts = []; for n in take(naturals(), 3) { firsts = firsts.push(n); } firsts",
+                "[0, 1, 2]",
+            ),
+            ("#bigs = []; for x in big([1, 5, 0, 7]) { bigs = bigs.push(x); } bigs", "[5, 7]"),
+            ("yield 1;", "yield can only be used as a statement of a generator function"),
+            ("fn selfish() { for x in me { yield x; } } #me = selfish(); me.next()", "generator is already running"),
+        ]);
+    }
+
+    #[test]
+    fn test_tail_calls() {
+        assert_eval(&[
+            (
+                "fn count_down(n) { if n == 0 { ret \"done\"; } ret count_down(n - 1); }
+                count_down(200000)",
+                "done",
+            ),
+            (
+                "fn is_even(n) { if n == 0 { ret true; } ret is_odd(n - 1); }
+                fn is_odd(n) { if n == 0 { ret false; } ret is_even(n - 1); }
+                is_even(100001)",
+                "false",
+            ),
+            (
+                "fn sum(n, acc) { ret if n == 0 { acc } else { ret sum(n - 1, acc + n); }; } sum(100000, 0)",
+                "5000050000",
+            ),
+            ("fn size(x) { ret x.len(); } fn wrap(x) { ret size(x); } wrap(\"abc\")", "3"),
+            ("ret count_down(3);", "done"),
+            ("fn missing() { ret nothing(1); } missing()", "unknown identifier nothing"),
+        ]);
+    }
+
+    #[test]
+    fn test_recursion_depth() {
+        let result = run_with_stack(STACK_SIZE, || {
+            assert_eval(&[
+                (
+                    "fn down(n) { if n == 0 { 0 } else { down(n - 1) + 1 } } down(999)",
+                    "999",
+                ),
+                (
+                    "fn up(n) { up(n + 1) + 1 } up(0)",
+                    "maximum recursion depth exceeded\n    in up\n    in up\n    in up\n    in up\n    in up",
+                ),
+                ("down(1000)", "maximum recursion depth exceeded\n    in down\n    in down\n    in down\n    in down\n    in down"),
+                ("down(999)", "999"),
+                (
+                    "struct Num { n } impl Num { fn add(self, o) { self + o } } Num(1) + Num(2)",
+                    "maximum recursion depth exceeded\n    in Num.add\n    in Num.add\n    in Num.add\n    in Num.add\n    in Num.add",
+                ),
+                (
+                    "#xs = [1]; xs.push(xs); #ys = [1]; ys.push(ys); xs == ys",
+                    "maximum recursion depth exceeded",
+                ),
+                // the calls in tail position run in a loop and don't count against the
+                // depth, so by default a tail recursion only stops where it returns
+                (
+                    "fn far(n) { if n == 0 { ret \"done\"; } ret far(n - 1); } far(50000)",
+                    "done",
+                ),
+                ("fn start(n) { ret down(n); } start(999)", "999"),
+                ("start(1000)", "maximum recursion depth exceeded\n    in down\n    in down\n    in down\n    in down\n    in down"),
+            ]);
+
+            set_tail_call_optimization(false);
+            assert_eval(&[(
+                "fn loop_on(n) { ret loop_on(n + 1); } loop_on(0)",
+                "maximum recursion depth exceeded\n    in loop_on\n    in loop_on\n    in loop_on\n    in loop_on\n    in loop_on",
+            )]);
+        });
+        assert!(result.is_ok());
+
+        // a depth the stack can't hold stops at the stack limit instead
+        let result = run_with_stack(8 * 1024 * 1024, || {
+            set_max_call_depth(100_000);
+            assert_eval(&[(
+                "fn up(n) { up(n + 1) + 1 } up(0)",
+                "maximum recursion depth exceeded\n    in up\n    in up\n    in up\n    in up\n    in up",
+            )]);
+        });
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_defer() {
+        assert_eval(&[
+            ("#log = \"\";", "null"),
+            (
+                "fn run() { defer log = log + \"a\"; defer log = log + \"b\"; log = log + \"body \"; }
+                run(); log",
+                "body ba",
+            ),
+            (
+                "fn early(n) { defer log = log + \" deferred\"; if n > 0 { ret n; } log = log + \" late\"; 0 }
+                log = \"\"; early(1)",
+                "1",
+            ),
+            ("log", " deferred"),
+            (
+                "fn fail() { defer log = \"cleaned up\"; missing } fail()",
+                "unknown identifier missing",
+            ),
+            ("log", "cleaned up"),
+            (
+                "fn outer() { defer log = log + \" outer\"; ret inner(); } fn inner() { log = \"inner\"; 1 }
+                outer(); log",
+                "inner outer",
+            ),
+            (
+                "fn gen() { defer log = \"finished\"; yield 1; yield 2; }
+                log = \"\"; #g = gen(); g.next(); log",
+                "",
+            ),
+            ("g.next(); g.next(); log", "finished"),
+        ]);
+    }
+
+    #[test]
+    fn test_hashes() {
+        assert_eval(&[
+            (
+                "#h = {\"one\": 1, \"two\": 2, 3: \"three\"}; h",
+                "{3: three, one: 1, two: 2}",
+            ),
+            ("h[\"two\"] + h[\"one\"]", "3"),
+            ("h[3]", "three"),
+            ("h.len()", "3"),
+            ("h.contains(\"one\")", "true"),
+            ("h.keys()", "[3, one, two]"),
+            ("h[\"four\"]", "key four is not in the hash"),
+            ("{[1]: 1}", "type array can not be a hash key"),
+            ("{ #a = 1; a + 1 }", "2"),
+            ("{ h; 10 }", "10"),
+            ("#e = {}; e", "{}"),
+            ("e[\"a\"] = 1; e", "{a: 1}"),
+            ("[{}, {}].len()", "2"),
+            ("{}", "null"),
+        ]);
+    }
+
+    #[test]
+    fn test_spread() {
+        assert_eval(&[
+            (
+                "fn add(a, b, c) { a + b + c } #args = [1, 2, 3]; add(...args)",
+                "6",
+            ),
+            ("fn wrap(args) { add(...args) } wrap((4, 5, 6))", "15"),
+            ("add(1, ...[2, 3])", "6"),
+            ("#a = [1, 2]; #b = [3]; [...a, ...b, 4]", "[1, 2, 3, 4]"),
+            ("[...\"ab\", ...(1,)]", "[a, b, 1]"),
+            ("fn gen() { yield 1; yield 2; } [0, ...gen()]", "[0, 1, 2]"),
+            ("#t = (...a, 5); t", "(1, 2, 5)"),
+            (
+                "#defaults = {\"a\": 1, \"b\": 2}; { ...defaults, \"b\": 3 }",
+                "{a: 1, b: 3}",
+            ),
+            ("{ \"a\": 0, ...defaults }", "{a: 1, b: 2}"),
+            ("[...1]", "type integer is not iterable"),
+            (
+                "{ ...a }",
+                "expected a hash to spread into the hash but got array",
+            ),
+            (
+                "(...a)",
+                "spread can only be used in the arguments of a call or a collection",
+            ),
+            (
+                "add(...[1, 2])",
+                "wrong number of arguments! wanted 3 got 2",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_comprehensions() {
+        assert_eval(&[
+            ("#xs = [-1, 2, -3, 4];", "null"),
+            ("[x * 2 for x in xs if x > 0]", "[4, 8]"),
+            ("[x for x in xs if x > 10]", "[]"),
+            ("[ch.upper() for ch in \"abc\"]", "[A, B, C]"),
+            ("{x: x * x for x in [1, 2, 3]}", "{1: 1, 2: 4, 3: 9}"),
+            ("x", "unknown identifier x"),
+            (
+                "fn evens() { for #i = 0; i < 6; i++ { if i / 2 * 2 == i { yield i; } } }
+                [n + 1 for n in evens()]",
+                "[1, 3, 5]",
+            ),
+            ("fn first_big(xs) { [if x > 2 { ret x; } for x in xs]; ret 0; } first_big([1, 5, 3])", "5"),
+            ("[y for y in 10]", "type integer is not iterable"),
+        ]);
+    }
+
+    #[test]
+    fn test_structs() {
+        assert_eval(&[
+            ("struct Point { x, y, }", "null"),
+            ("#p = Point(1, 2); p", "Point { x: 1, y: 2 }"),
+            ("p.x + p.y", "3"),
+            ("p.z", "type Point has no field z"),
+            ("Point(1)", "wrong number of arguments! wanted 2 got 1"),
+            ("impl Point { fn sum(self) { self.x + self.y } }", "null"),
+            ("p.sum()", "3"),
+            (
+                "impl Point { fn sum(self) { 0 } }",
+                "method sum is already defined for Point",
+            ),
+            ("p.missing()", "unknown method missing for type Point"),
+            (
+                "struct Pair { a, a }",
+                "field a is declared more than once in struct Pair",
+            ),
+            ("impl p { }", "impl expects a struct but p is Point"),
+            ("1.x", "field access is not supported for type integer"),
+        ]);
+    }
+
+    #[test]
+    fn test_mutable_collections() {
+        assert_eval(&[
+            ("#xs = [1, 2, 3]; #ys = xs; ys[0] = 5; xs", "[5, 2, 3]"),
+            ("fn zero(a) { a[1] = 0; } zero(xs); ys", "[5, 0, 3]"),
+            ("#m = {\"a\": 1}; #n = m; n[\"b\"] = 2; m", "{a: 1, b: 2}"),
+            ("m[\"a\"] = m[\"a\"] + 10", "11"),
+            (
+                "struct Point { x, y } #p = Point(1, 2); #q = p; q.x = 7; p",
+                "Point { x: 7, y: 2 }",
+            ),
+            (
+                "#grid = [[1, 2], [3, 4]]; grid[1][0] = 9; grid",
+                "[[1, 2], [9, 4]]",
+            ),
+            ("const ZS = [1]; ZS[0] = 2; ZS", "[2]"),
+            ("#c = copy(xs); c[0] = 1; xs", "[5, 0, 3]"),
+            ("#d = copy(grid); d[0][0] = 8; grid", "[[8, 2], [9, 4]]"),
+            (
+                "#e = deep_copy(grid); e[0][0] = 1; [grid, e]",
+                "[[[8, 2], [9, 4]], [[1, 2], [9, 4]]]",
+            ),
+            (
+                "#f = deep_copy(p); f.y = 0; [p, f]",
+                "[Point { x: 7, y: 2 }, Point { x: 7, y: 0 }]",
+            ),
+            (
+                "#r = []; r.push(r); #s = deep_copy(r); s[0][0][0].len()",
+                "1",
+            ),
+            ("r", "[[...]]"),
+            (
+                "#g = {}; g[\"self\"] = g; g[\"r\"] = r; g",
+                "{r: [[...]], self: {...}}",
+            ),
+            (
+                "#q = Point(1, 2); q.x = q; q",
+                "Point { x: Point {...}, y: 2 }",
+            ),
+            ("[r, r]", "[[[...]], [[...]]]"),
+            ("xs[3] = 1", "index 3 is out of range for array of length 3"),
+            ("xs[\"a\"] = 1", "index must be an integer but got string"),
+            (
+                "(1, 2)[0] = 3",
+                "index assignment is not supported for type tuple",
+            ),
+            ("p.z = 1", "type Point has no field z"),
+            (
+                "copy(1, 2)",
+                "wrong number of arguments for copy! wanted 1 got 2",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_operator_overloading() {
+        assert_eval(&[
+            (
+                "struct Vector { x, y }
+                impl Vector {
+                    fn add(self, other) { Vector(self.x + other.x, self.y + other.y) }
+                    fn mul(self, k) { Vector(self.x * k, self.y * k) }
+                    fn rmul(self, k) { self * k }
+                    fn eq(self, other) { self.x == other.x ? self.y == other.y : false }
+                    fn lt(self, other) { self.x * self.x + self.y * self.y < other.x * other.x + other.y * other.y }
+                    fn index(self, i) { i == 0 ? self.x : self.y }
+                    fn to_string(self) { \"<\" + self.x + \", \" + self.y + \">\" }
+                }
+                #a = Vector(1, 2); #b = Vector(3, 4);",
+                "null",
+            ),
+            ("a + b", "<4, 6>"),
+            ("a * 3", "<3, 6>"),
+            ("a == Vector(1, 2)", "true"),
+            ("a != b", "true"),
+            ("a < b", "true"),
+            ("b[0] + b[1]", "7"),
+            ("[a, b]", "[<1, 2>, <3, 4>]"),
+            ("a - b", "type Vector does not define the sub method"),
+            ("2 * a", "<2, 4>"),
+            ("1 <= a", "type Vector does not define the ge method"),
+            ("2 + a", "type Vector does not define the radd method"),
+            (
+                "struct P { x } #p = P(1); #q = P(1); [p == p, p == q, p != q, p != p]",
+                "[true, false, true, false]",
+            ),
+            ("if p == p { \"same\" } else { \"other\" }", "same"),
+            ("p == 1", "false"),
+            ("1 != p", "true"),
+        ]);
+    }
+
+    #[test]
+    fn test_to_string() {
+        assert_eval(&[
+            (
+                "struct Temp { degrees } impl Temp { fn to_string(self) { self.degrees + \"C\" } }
+                struct Broken { x } impl Broken { fn to_string(self) { 1 / 0 } }",
+                "null",
+            ),
+            ("Temp(21)", "21C"),
+            (
+                "[Temp(1), (Temp(2),), {\"t\": Temp(3)}]",
+                "[1C, (2C,), {t: 3C}]",
+            ),
+            ("\"now \" + Temp(5) + \"!\"", "now 5C!"),
+            ("[Temp(1), 2].join(\", \")", "1C, 2"),
+            ("Broken(1)", "division by zero"),
+            ("println(Broken(1)); 1", "division by zero"),
+            ("[Broken(1)]", "division by zero"),
+            ("\"x: \" + Broken(1)", "division by zero"),
+            ("[Broken(1)].join(\"\")", "division by zero"),
+        ]);
+    }
+
+    #[test]
+    fn test_traits() {
+        assert_eval(&[
+            (
+                "trait Shape {
+                    fn area(self);
+                    fn double_area(self) { self.area() * 2 }
+                }
+                struct Square { side }
+                struct Circle { r }
+                impl Shape for Square { fn area(self) { self.side * self.side } }",
+                "null",
+            ),
+            ("Square(3).double_area()", "18"),
+            ("implements(Square(3), Shape)", "true"),
+            ("implements(Circle(1), Shape)", "false"),
+            ("implements(1, Shape)", "false"),
+            ("implements(Square(3), Square)", "expected a trait as argument 1 of implements but got struct"),
+            (
+                "impl Shape for Circle { fn double_area(self) { 0 } }",
+                "impl Shape for Circle is missing the method area",
+            ),
+            ("implements(Circle(1), Shape)", "false"),
+            (
+                "impl Shape for Circle { fn area(self) { 3 } fn perimeter(self) { 6 } }",
+                "method perimeter is not a member of trait Shape",
+            ),
+            (
+                "impl Shape for Circle { fn area(self, scale) { 3 } }",
+                "method area of trait Shape takes 1 parameters but got 2",
+            ),
+            (
+                "impl Shape for Circle { fn area(self) { 3 * self.r * self.r } fn double_area(self) { 0 } }
+                Circle(2).area()",
+                "12",
+            ),
+            ("Circle(2).double_area()", "0"),
+            ("impl Shape for Circle { fn area(self) { 0 } }", "Circle already implements Shape"),
+            ("impl Square for Circle { }", "impl expects a trait but Square is struct"),
+            ("trait Twice { fn a(self); fn a(self) {} }", "method a is declared more than once in trait Twice"),
+        ]);
+    }
+
+    #[test]
+    fn test_tuples() {
+        assert_eval(&[
+            ("(1, \"two\", [3])", "(1, two, [3])"),
+            ("(1,)", "(1,)"),
+            ("()", "()"),
+            ("(1 + 2) * 3", "9"),
+            ("(1, 2)[1]", "2"),
+            ("(1, 2)[2]", "index 2 is out of range for tuple of length 2"),
+            ("#(q, r) = divmod(7, 2); q * 10 + r", "31"),
+            ("divmod(-7, 2)", "(-3, -1)"),
+            ("divmod(1, 0)", "division by zero"),
+            (
+                "fn parse(s) { if s == \"\" { ret 0, \"empty input\"; } ret s, \"ok\"; }
+                #(value, status) = parse(\"\"); status",
+                "empty input",
+            ),
+            ("const (a, b) = parse(\"x\"); a + b", "xok"),
+            ("a = 1", "assigning to constant a is not allowed"),
+            (
+                "#(x, y) = (1, 2, 3);",
+                "expected a tuple of 2 values but got 3",
+            ),
+            (
+                "#(x, y) = [1, 2];",
+                "expected a tuple of 2 values but got array",
+            ),
+            ("#t = 0; for item in (1, 2, 3) { t = t + item; } t", "6"),
+            ("(1, 2) == (1, 2)", "true"),
+            ("(1, 2) != (1, 2)", "false"),
+            ("(1, 2) == (1, 2, 3)", "false"),
+            ("(1, (2, \"x\")) == (1, (2, \"x\"))", "true"),
+            ("[1, [2, 3]] == [1, [2, 3]]", "true"),
+            ("[1, [2, 3]] != [1, [2, 4]]", "true"),
+            ("[1] == (1,)", "false"),
+            (
+                "#h = {\"a\": 1, \"b\": [2]}; h == {\"b\": [2], \"a\": 1.0d}",
+                "true",
+            ),
+            ("#h = {\"a\": 1}; h == {\"b\": 1}", "false"),
+            ("#h = {\"a\": 1}; h != {\"a\": 2}", "true"),
+            (
+                "struct P { x } #p = P(1); [(p, 1) == (p, 1), (p, 1) == (P(1), 1)]",
+                "[true, false]",
+            ),
+            ("#xs = [1]; xs.push(xs); xs == xs", "true"),
+            (
+                "(1, 2) < (1, 2)",
+                "eval infix not available for operator: <",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_quote_and_unquote() {
+        assert_eval(&[
+            ("quote(1 + a)", "quote((1 + a))"),
+            ("#x = 5; quote(unquote(x) * 2)", "quote((5 * 2))"),
+            (
+                "quote(unquote(quote(a)) - unquote([1, true]))",
+                "quote((a - [1, true]))",
+            ),
+            ("quote(1 + 2).source()", "(1 + 2)"),
+            (
+                "quote(1, 2)",
+                "wrong number of arguments for quote! wanted 1 got 2",
+            ),
+            ("unquote(x)", "unquote can only be used inside of quote"),
+            ("quote(unquote(len))", "unknown identifier len"),
+            (
+                "fn quote(x) { x * 2 } println(quote(3));",
+                "redeclaring built-in function quote is not allowed",
+            ),
+            (
+                "fn unquote(x) { x }",
+                "redeclaring built-in function unquote is not allowed",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_macros() {
+        assert_eval(&[
+            (
+                "macro unless(condition, consequent, alternate) {
+                    quote(if !(unquote(condition)) { unquote(consequent) } else { unquote(alternate) })
+                }
+                unless(10 > 5, \"not greater\", \"greater\")",
+                "greater",
+            ),
+            ("unless(1 > 5, unless(false, 1, 2), 3)", "1"),
+            (
+                "macro assert_eq(actual, expected) {
+                    quote(if unquote(actual) == unquote(expected) { \"ok\" } else {
+                        unquote(actual.source()) + \" != \" + unquote(expected.source())
+                    })
+                }
+                assert_eq(1 + 1, 3)",
+                "(1 + 1) != 3",
+            ),
+            ("fn two() { assert_eq(1 + 1, 2) } two()", "ok"),
+            // the tmp of the macro doesn't capture the tmp of the caller
+            (
+                "macro add_first(a, b) { quote({ #tmp = unquote(a); tmp + unquote(b) }) }
+                #tmp = 10; add_first(1, tmp)",
+                "11",
+            ),
+            ("tmp", "10"),
+            (
+                "macro bad(x) { 1 } bad(2)",
+                "macro bad must return a quote but returned integer",
+            ),
+            (
+                "fn nested() { macro m() { quote(1) } } nested()",
+                "macros can only be defined at the top level",
+            ),
+            (
+                "macro quote(x) { x } quote(1)",
+                "redeclaring built-in function quote is not allowed",
+            ),
+            (
+                "macro println(x) { x }",
+                "redeclaring built-in function println is not allowed",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_eval_and_load() {
+        let path = std::env::temp_dir().join("taha_evaluator_test_load.taha");
+        std::fs::write(&path, "#loaded = 40; fn plugin() { loaded + 2 } plugin()").unwrap();
+        let path = path.to_string_lossy().replace('\\', "/");
+
+        assert_eval(&[
+            ("eval(\"1 + 2\")", "3"),
+            ("#x = 1; eval(\"x\")", "unknown identifier x"),
+            ("eval(\"x + 1\", true)", "2"),
+            ("eval(\"#y = 5;\", true); y", "5"),
+            ("fn scoped() { eval(\"#z = 1;\", true); z } scoped()", "1"),
+            ("z", "unknown identifier z"),
+            (
+                "eval(\"1 +\")",
+                "parse error: no prefix function found for the token: EOF",
+            ),
+            (
+                "eval(\"0b12\")",
+                "parse error: invalid digit '2' in the integer literal 0b12",
+            ),
+            (
+                "eval(\"1 + 0x; 2\")",
+                "parse error: expected digits after 0x in the integer literal",
+            ),
+            (
+                "eval(1)",
+                "expected a string as argument 0 of eval but got integer",
+            ),
+            (
+                "eval(\"1\", 1)",
+                "expected a boolean as argument 1 of eval but got integer",
+            ),
+            (&format!("load(\"{}\")", path), "42"),
+            ("loaded", "unknown identifier loaded"),
+            (&format!("load(\"{}\", true); plugin()", path), "42"),
+            (
+                "load(\"missing.taha\")",
+                "could not load missing.taha: No such file or directory (os error 2)",
+            ),
+            (
+                "fn load(p) { \"loaded \" + p } load(\"cfg\")",
+                "redeclaring built-in function load is not allowed",
+            ),
+            (
+                "fn eval(s) { s }",
+                "redeclaring built-in function eval is not allowed",
+            ),
+            ("eval", "[builtin func]"),
+            ("#e = eval; e(\"1 + 2\")", "3"),
+            (
+                "#w = 3; #run = eval; fn f() { ret run(\"w * 2\", true); } f()",
+                "6",
+            ),
+            ("fn apply(f, s) { f(s) } apply(eval, \"2 * 3\")", "6"),
+        ]);
+    }
+
+    #[test]
+    fn test_unicode_strings() {
         assert_eval(&[
-            ("fn foo_bar(a, b) { ret a + b; }", "null")
+            ("#پیام = \"سلام دنیا 👋\"; پیام", "سلام دنیا 👋"),
+            ("پیام + \"، 世界\"", "سلام دنیا 👋، 世界"),
+            ("پیام.len()", "11"),
+            ("پیام[10]", "👋"),
         ]);
     }
 }
@@ -0,0 +1,285 @@
+use crate::{
+    defer, eval_expression, eval_statement, eval_variable_declaration, is_truthy, make_iterator,
+    new_enclosed_env, next_item, stack,
+};
+use ast::statement::{For, If, Return, Statement, Yield};
+use object::{
+    env::Env,
+    generator::{Generator, GeneratorFrame},
+    object::{EvalError, Object},
+};
+use std::{cell::RefCell, rc::Rc};
+
+enum Action {
+    Next,
+    Push(GeneratorFrame),
+    Pop,
+    Yield(Rc<Object>),
+    Finish,
+    Break(Option<String>),
+    Continue(Option<String>),
+}
+
+// The action of a signal that skips the rest of the block, None when the
+// value is not a signal.
+fn jump_action(value: &Object) -> Option<Action> {
+    match value {
+        Object::ReturnValue(_) => Some(Action::Finish),
+        Object::Break(label) => Some(Action::Break(label.clone())),
+        Object::Continue(label) => Some(Action::Continue(label.clone())),
+        _ => None,
+    }
+}
+
+// Tells whether the statements yield. The bodies of nested functions
+// don't count since they belong to another function.
+pub fn contains_yield(statements: &[Statement]) -> bool {
+    statements.iter().any(|statement| match statement {
+        Statement::Yield(_) => true,
+        Statement::Block(block) => contains_yield(&block.body),
+        Statement::For(for_stmt) => contains_yield(&for_stmt.body.body),
+        Statement::ForIn(for_in) => contains_yield(&for_in.body.body),
+        Statement::If(if_stmt) => if_contains_yield(if_stmt),
+        _ => false,
+    })
+}
+
+fn if_contains_yield(if_stmt: &If) -> bool {
+    contains_yield(&if_stmt.consequent.body)
+        || if_stmt.branches.iter().any(if_contains_yield)
+        || if_stmt
+            .alternate
+            .as_ref()
+            .is_some_and(|alternate| contains_yield(&alternate.body))
+}
+
+// Runs the body of the generator until the next yield,
+// None means the generator is finished and can't be resumed anymore.
+pub fn resume_generator(
+    generator: &Rc<RefCell<Generator>>,
+) -> Result<Option<Rc<Object>>, EvalError> {
+    // the frames are taken out while running, so the body is free to use the generator object
+    let mut frames = {
+        let mut generator = generator.borrow_mut();
+
+        if generator.running {
+            return Err("generator is already running".to_string());
+        }
+
+        generator.running = true;
+        defer::push_frame(std::mem::take(&mut generator.deferred));
+        std::mem::take(&mut generator.frames)
+    };
+
+    let result = stack::enter().and_then(|_| {
+        let result = run_frames(&mut frames);
+        stack::leave();
+        result
+    });
+    let deferred = defer::pop_frame();
+
+    {
+        let mut generator = generator.borrow_mut();
+        generator.running = false;
+
+        // a generator that finished or failed is never resumed again
+        if let Ok(Some(_)) = result {
+            generator.frames = frames;
+            generator.deferred = deferred;
+            return result;
+        }
+    }
+
+    defer::run_frame(deferred, result)
+}
+
+fn run_frames(frames: &mut Vec<GeneratorFrame>) -> Result<Option<Rc<Object>>, EvalError> {
+    loop {
+        let action = match frames.last_mut() {
+            None => return Ok(None),
+            Some(GeneratorFrame::Block {
+                statements,
+                index,
+                env,
+            }) => match statements.get(*index).cloned() {
+                Some(statement) => {
+                    *index += 1;
+                    run_statement(statement, &env.clone())?
+                }
+                None => Action::Pop,
+            },
+            Some(GeneratorFrame::For { stmt, started, env }) => {
+                let run_increment = *started;
+                *started = true;
+                step_for_loop(stmt, run_increment, env)?
+            }
+            Some(GeneratorFrame::ForIn {
+                variable,
+                iterator,
+                body,
+                env,
+                ..
+            }) => match next_item(iterator)? {
+                Some(item) => {
+                    let scope = new_enclosed_env(env);
+                    scope.borrow_mut().set(variable.clone(), item)?;
+
+                    Action::Push(GeneratorFrame::Block {
+                        statements: body.body.clone(),
+                        index: 0,
+                        env: scope,
+                    })
+                }
+                None => Action::Pop,
+            },
+        };
+
+        match action {
+            Action::Next => {}
+            Action::Push(frame) => frames.push(frame),
+            Action::Pop => {
+                frames.pop();
+            }
+            Action::Yield(value) => return Ok(Some(value)),
+            Action::Finish => {
+                frames.clear();
+                return Ok(None);
+            }
+            Action::Break(label) => {
+                unwind_to_loop(frames, &label)?;
+                frames.pop();
+            }
+            Action::Continue(label) => unwind_to_loop(frames, &label)?,
+        }
+    }
+}
+
+// Drops the frames of the blocks inside of the loop the break or continue
+// belongs to, the frame of the loop is left on the top.
+fn unwind_to_loop(
+    frames: &mut Vec<GeneratorFrame>,
+    label: &Option<String>,
+) -> Result<(), EvalError> {
+    while let Some(frame) = frames.last() {
+        let loop_label = match frame {
+            GeneratorFrame::For { stmt, .. } => Some(stmt.label.as_ref().map(|label| &label.name)),
+            GeneratorFrame::ForIn { label, .. } => Some(label.as_ref()),
+            GeneratorFrame::Block { .. } => None,
+        };
+
+        if let Some(loop_label) = loop_label {
+            if label.is_none() || label.as_ref() == loop_label {
+                return Ok(());
+            }
+        }
+
+        frames.pop();
+    }
+
+    Err("break and continue can only be used inside of a loop".to_string())
+}
+
+fn run_statement(statement: Statement, env: &Env) -> Result<Action, EvalError> {
+    match statement {
+        Statement::Yield(Yield { argument, .. }) => {
+            let value = eval_expression(argument, env)?;
+
+            Ok(jump_action(&value).unwrap_or(Action::Yield(value)))
+        }
+        Statement::Return(Return { argument, .. }) => {
+            eval_expression(argument, env)?;
+            Ok(Action::Finish)
+        }
+        Statement::Block(block) => Ok(Action::Push(GeneratorFrame::Block {
+            statements: block.body,
+            index: 0,
+            env: new_enclosed_env(env),
+        })),
+        Statement::If(if_stmt) => {
+            let branches = std::iter::once((&if_stmt.condition, &if_stmt.consequent)).chain(
+                if_stmt
+                    .branches
+                    .iter()
+                    .map(|branch| (&branch.condition, &branch.consequent)),
+            );
+
+            let mut selected = None;
+            for (condition, consequent) in branches {
+                let condition = eval_expression(condition.clone(), env)?;
+
+                if let Some(action) = jump_action(&condition) {
+                    return Ok(action);
+                }
+
+                if is_truthy(&condition) {
+                    selected = Some(consequent);
+                    break;
+                }
+            }
+
+            Ok(match selected.or(if_stmt.alternate.as_ref()) {
+                Some(block) => Action::Push(GeneratorFrame::Block {
+                    statements: block.body.clone(),
+                    index: 0,
+                    env: new_enclosed_env(env),
+                }),
+                None => Action::Next,
+            })
+        }
+        Statement::For(for_stmt) => {
+            let env = new_enclosed_env(env);
+
+            if let Some(var) = &for_stmt.initializer {
+                eval_variable_declaration(&var.identifier, var.expr.clone(), &env)?;
+            }
+
+            Ok(Action::Push(GeneratorFrame::For {
+                stmt: Box::new(for_stmt),
+                started: false,
+                env,
+            }))
+        }
+        Statement::ForIn(for_in) => {
+            let iterable = eval_expression(for_in.iterable, env)?;
+
+            Ok(Action::Push(GeneratorFrame::ForIn {
+                variable: for_in.variable.name,
+                iterator: make_iterator(&iterable)?,
+                body: *for_in.body,
+                label: for_in.label.map(|label| label.name),
+                env: env.clone(),
+            }))
+        }
+        statement => {
+            let result = eval_statement(&statement, env)?;
+
+            Ok(jump_action(&result).unwrap_or(Action::Next))
+        }
+    }
+}
+
+fn step_for_loop(stmt: &For, run_increment: bool, env: &Env) -> Result<Action, EvalError> {
+    if run_increment {
+        if let Some(increment) = &stmt.increment {
+            eval_expression(increment.clone(), env)?;
+        }
+    }
+
+    let condition = match &stmt.condition {
+        Some(condition) => eval_expression(condition.clone(), env)?,
+        None => return Ok(Action::Pop),
+    };
+
+    match &*condition {
+        Object::Boolean(true) => Ok(Action::Push(GeneratorFrame::Block {
+            statements: stmt.body.body.clone(),
+            index: 0,
+            env: new_enclosed_env(env),
+        })),
+        Object::Boolean(false) => Ok(Action::Pop),
+        ty => Err(format!(
+            "only bool type is valid for the condition of a for_statement but got {}",
+            ty
+        )),
+    }
+}
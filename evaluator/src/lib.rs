@@ -1,52 +1,131 @@
 use ast::{
     expression::{
-        Boolean, Expression, FunctionCall, Identifier, Integer, Literal, StringType,
-        UnaryExpression, UnaryOperator,
+        Array, Assignment, BigInteger, Boolean, Bytes, Comprehension, Conditional, Expression,
+        Field, FunctionCall, Hash, HashComprehension, HashEntry, Identifier, Index, Integer,
+        ListComprehension, Literal, MethodCall, Set, Spread, StringType, Tuple, UnaryExpression,
+        UnaryOperator,
+    },
+    statement::{
+        BlockStatement, Break, Continue, Defer, ForIn, Function, If, Impl, Return, Statement,
+        Struct, Trait, TupleDeclaration, Variable,
     },
-    statement::{BlockStatement, Function, If, Return, Statement, Variable},
     Node,
 };
-use builtins::BUILT_INS;
+use builtins::{lookup_method, BUILT_INS};
+use num_bigint::{BigInt, Sign};
 use object::{
+    decimal::Decimal,
     env::{Env, Environment},
+    generator::{Generator, ObjectIterator},
+    hash::HashKey,
+    instance::{set_display_hook, Instance, StructType, TraitType},
     object::{EvalError, Object},
 };
-use std::{borrow::Borrow, cell::RefCell, rc::Rc};
-use token::{Token, TokenKind};
+use std::{
+    borrow::Borrow,
+    cell::{Cell, RefCell},
+    collections::BTreeMap,
+    rc::Rc,
+};
+use token::{Span, Token, TokenKind};
 
+mod defer;
 mod evaluator_test;
+mod generator;
+mod macros;
+mod set;
+mod slice;
+mod source;
+mod stack;
+
+pub use macros::expand_macros;
+pub use source::eval_source;
+pub use stack::{run_with_stack, set_max_call_depth, STACK_SIZE};
+
+// A `ret`, `break` or `continue` can be nested inside of an if or a block
+// expression, in that case the signal must skip the rest of the enclosing expression.
+macro_rules! eval_operand {
+    ($expr:expr, $env:expr) => {{
+        let val = eval_expression($expr, $env)?;
+
+        if val.is_jump() {
+            return Ok(val);
+        }
+
+        val
+    }};
+}
+
+thread_local! {
+    static TAIL_CALLS: Cell<bool> = const { Cell::new(true) };
+}
+
+// Tail calls are run without growing the stack by default, disabling it keeps
+// every call on the stack which is handy while debugging.
+pub fn set_tail_call_optimization(enabled: bool) {
+    TAIL_CALLS.with(|tail_calls| tail_calls.set(enabled));
+}
 
 pub fn eval(node: Node, env: &Env) -> Result<Rc<Object>, EvalError> {
+    set_display_hook(display_instance);
+
     match node {
-        Node::Program(program) => eval_block_statements(&program.body, env),
+        Node::Program(program) => {
+            defer::push_frame(vec![]);
+            let result = eval_block_statements(&program.body, env).and_then(unwrap_return);
+            let result = defer::run_frame(defer::pop_frame(), result);
+
+            resolve_tail_calls(result?)
+        }
         Node::Statement(statement) => eval_statement(&statement, env),
         Node::Expression(expression) => eval_expression(expression, env),
     }
 }
 
-fn eval_block_statements(statements: &Vec<Statement>, env: &Env) -> Result<Rc<Object>, EvalError> {
+fn eval_block_statements(statements: &[Statement], env: &Env) -> Result<Rc<Object>, EvalError> {
     let mut result = Rc::new(Object::Null);
 
     for statement in statements {
         result = eval_statement(statement, env)?;
+
+        // a return, break or continue stops the evaluation of all the enclosing blocks
+        if result.is_jump() {
+            return Ok(result);
+        }
     }
 
     Ok(result)
 }
 
+// Evaluates the block in a scope of its own, so the variables declared
+// inside of it are dropped as soon as the block ends.
+fn eval_scoped_block(block: &BlockStatement, env: &Env) -> Result<Rc<Object>, EvalError> {
+    eval_block_statements(&block.body, &new_enclosed_env(env))
+}
+
+fn new_enclosed_env(env: &Env) -> Env {
+    Rc::new(RefCell::new(Environment::new_enclosed_environment(env)))
+}
+
 fn eval_statement(statement: &Statement, env: &Env) -> Result<Rc<Object>, EvalError> {
     match statement {
         Statement::For(for_stmt) => eval_for_statement(
             for_stmt.initializer.clone(),
             for_stmt.condition.clone(),
             for_stmt.increment.clone(),
-            for_stmt.body.clone(),
+            &for_stmt.body,
+            &for_stmt.label,
             &env.clone(),
         ),
         Statement::VariableDeclaration(variable) => {
             eval_variable_declaration(&variable.identifier, variable.expr.clone(), env)
         }
+        Statement::ConstantDeclaration(constant) => {
+            eval_constant_declaration(&constant.identifier, constant.expr.clone(), env)
+        }
+        Statement::TupleDeclaration(declaration) => eval_tuple_declaration(declaration, env),
         Statement::Expression(expression) => eval_expression(expression.clone(), env),
+        Statement::Block(block) => eval_scoped_block(block, env),
         Statement::If(If {
             condition,
             consequent,
@@ -55,9 +134,50 @@ fn eval_statement(statement: &Statement, env: &Env) -> Result<Rc<Object>, EvalEr
             ..
         }) => eval_if_statement(condition, consequent, alternate, branches, env),
         Statement::Return(Return { argument, .. }) => eval_return_statement(argument, env),
+        Statement::Defer(Defer { argument, .. }) => defer::register(argument.clone(), env),
+        Statement::Yield(_) => {
+            Err("yield can only be used as a statement of a generator function".to_string())
+        }
+        Statement::ForIn(for_in) => eval_for_in_statement(for_in, env),
         Statement::Function(Function {
             name, params, body, ..
         }) => eval_function_statement(name.clone(), params.clone(), *body.clone(), &env.clone()),
+        Statement::Struct(struct_stmt) => eval_struct_statement(struct_stmt, env),
+        Statement::Impl(impl_stmt) => eval_impl_statement(impl_stmt, env),
+        Statement::Trait(trait_stmt) => eval_trait_statement(trait_stmt, env),
+        // the top level macros are taken out of the program by expand_macros
+        Statement::Macro(_) => Err("macros can only be defined at the top level".to_string()),
+        Statement::Break(Break { label, .. }) => Ok(Rc::new(Object::Break(label_name(label)))),
+        Statement::Continue(Continue { label, .. }) => {
+            Ok(Rc::new(Object::Continue(label_name(label))))
+        }
+    }
+}
+
+fn label_name(label: &Option<Identifier>) -> Option<String> {
+    label.as_ref().map(|label| label.name.clone())
+}
+
+enum LoopFlow {
+    Next,
+    Break,
+    Exit(Rc<Object>),
+}
+
+// Tells what the loop does after its body, a break or continue belongs to the
+// loop when it has no label or names the label of the loop. The signals of the
+// outer loops and the returns leave the loop as they are.
+fn loop_flow(result: Rc<Object>, label: &Option<Identifier>) -> LoopFlow {
+    let belongs = |target: &Option<String>| match target {
+        Some(target) => label.as_ref().is_some_and(|label| &label.name == target),
+        None => true,
+    };
+
+    match &*result {
+        Object::Break(target) if belongs(target) => LoopFlow::Break,
+        Object::Continue(target) if belongs(target) => LoopFlow::Next,
+        obj if obj.is_jump() => LoopFlow::Exit(result),
+        _ => LoopFlow::Next,
     }
 }
 
@@ -65,101 +185,444 @@ fn eval_for_statement(
     initializer: Option<Variable>,
     condition: Option<Expression>,
     increment: Option<Expression>,
-    body: Box<BlockStatement>,
+    body: &BlockStatement,
+    label: &Option<Identifier>,
     env: &Env,
 ) -> Result<Rc<Object>, EvalError> {
+    // the loop counter lives in the scope of the loop and doesn't survive it
+    let env = &new_enclosed_env(env);
+
     if let Some(var) = initializer {
         eval_variable_declaration(&var.identifier.clone(), var.expr.clone(), env)?;
     }
 
-    loop {
-        if let Some(ref expr) = condition {
-            match &*eval_expression(expr.clone(), env)? {
-                Object::Boolean(value) => {
-                    if *value == true {
-                        eval_block_statements(&body.body, env)?;
+    while let Some(ref expr) = condition {
+        match &*eval_expression(expr.clone(), env)? {
+            Object::Boolean(value) => {
+                if *value {
+                    match loop_flow(eval_scoped_block(body, env)?, label) {
+                        LoopFlow::Next => {}
+                        LoopFlow::Break => break,
+                        LoopFlow::Exit(result) => return Ok(result),
+                    }
 
-                        if let Some(ref expr) = increment {
-                            eval_expression(expr.clone(), env)?;
-                        }
-                    } else {
-                        break;
+                    if let Some(ref expr) = increment {
+                        eval_expression(expr.clone(), env)?;
                     }
-                }
-                ty @ _ => {
-                    return Err(format!(
-                        "only bool type is valid for the condition of a for_statement but got {}",
-                        ty
-                    ))
+                } else {
+                    break;
                 }
             }
-        } else {
-            break;
+            ty => {
+                return Err(format!(
+                    "only bool type is valid for the condition of a for_statement but got {}",
+                    ty
+                ))
+            }
+        }
+    }
+
+    Ok(Rc::new(Object::Null))
+}
+
+fn eval_for_in_statement(for_in: &ForIn, env: &Env) -> Result<Rc<Object>, EvalError> {
+    let iterable = eval_operand!(for_in.iterable.clone(), env);
+    let mut iterator = make_iterator(&iterable)?;
+
+    while let Some(item) = next_item(&mut iterator)? {
+        let scope = new_enclosed_env(env);
+        scope.borrow_mut().set(for_in.variable.name.clone(), item)?;
+
+        match loop_flow(
+            eval_block_statements(&for_in.body.body, &scope)?,
+            &for_in.label,
+        ) {
+            LoopFlow::Next => {}
+            LoopFlow::Break => break,
+            LoopFlow::Exit(result) => return Ok(result),
+        }
+    }
+
+    Ok(Rc::new(Object::Null))
+}
+
+// Every item of a comprehension gets a scope of its own, so the variable of the
+// comprehension doesn't leak into the enclosing scope. `collect` is called with
+// the scope of the items that pass the condition.
+fn eval_comprehension<F>(
+    comprehension: &Comprehension,
+    env: &Env,
+    mut collect: F,
+) -> Result<Rc<Object>, EvalError>
+where
+    F: FnMut(&Env) -> Result<Rc<Object>, EvalError>,
+{
+    let iterable = eval_operand!((*comprehension.iterable).clone(), env);
+    let mut iterator = make_iterator(&iterable)?;
+
+    while let Some(item) = next_item(&mut iterator)? {
+        let scope = new_enclosed_env(env);
+        scope
+            .borrow_mut()
+            .set(comprehension.variable.name.clone(), item)?;
+
+        if let Some(condition) = &comprehension.condition {
+            let condition = eval_operand!((**condition).clone(), &scope);
+
+            if !is_truthy(&condition) {
+                continue;
+            }
+        }
+
+        let result = collect(&scope)?;
+
+        if result.is_jump() {
+            return Ok(result);
         }
     }
 
     Ok(Rc::new(Object::Null))
 }
 
+fn make_iterator(iterable: &Object) -> Result<ObjectIterator, EvalError> {
+    match iterable {
+        // the loop walks over the elements the array had when it started
+        Object::Array(elements) => Ok(ObjectIterator::Elements(
+            RefCell::borrow(elements).clone().into_iter(),
+        )),
+        Object::Tuple(elements) => Ok(ObjectIterator::Elements(elements.clone().into_iter())),
+        Object::String(value) => Ok(ObjectIterator::Elements(
+            value
+                .chars()
+                .map(|ch| Rc::new(Object::String(ch.to_string())))
+                .collect::<Vec<Rc<Object>>>()
+                .into_iter(),
+        )),
+        Object::Hash(pairs) => Ok(ObjectIterator::Elements(
+            RefCell::borrow(pairs)
+                .keys()
+                .map(|key| Rc::new(key.to_object()))
+                .collect::<Vec<Rc<Object>>>()
+                .into_iter(),
+        )),
+        Object::Bytes(value) => Ok(ObjectIterator::Elements(
+            value
+                .iter()
+                .map(|byte| Rc::new(Object::Integer(*byte as i64)))
+                .collect::<Vec<Rc<Object>>>()
+                .into_iter(),
+        )),
+        Object::Set(elements) => Ok(ObjectIterator::Elements(
+            RefCell::borrow(elements)
+                .iter()
+                .map(|element| Rc::new(element.to_object()))
+                .collect::<Vec<Rc<Object>>>()
+                .into_iter(),
+        )),
+        Object::Generator(generator) => Ok(ObjectIterator::Generator(Rc::clone(generator))),
+        _ => Err(format!("type {} is not iterable", iterable.type_name())),
+    }
+}
+
+fn next_item(iterator: &mut ObjectIterator) -> Result<Option<Rc<Object>>, EvalError> {
+    match iterator {
+        ObjectIterator::Elements(elements) => Ok(elements.next()),
+        ObjectIterator::Generator(generator) => generator::resume_generator(generator),
+    }
+}
+
 fn eval_function_statement(
     name: String,
     params: Vec<Identifier>,
     body: BlockStatement,
     env: &Env,
 ) -> Result<Rc<Object>, EvalError> {
-    // we prevent overwriting built-in functions!
-    match BUILT_INS.borrow().get(name.as_str()) {
-        Some(_) => Err(format!(
+    check_built_in_name(&name)?;
+
+    let declare_fn = function_object(params, body, env);
+
+    env.borrow_mut().set(name, declare_fn)?;
+    Ok(Rc::new(Object::Null))
+}
+
+// we prevent overwriting built-in functions! quote and unquote are called by their
+// name, so a function or a macro of the same name would never run.
+fn check_built_in_name(name: &str) -> Result<(), EvalError> {
+    if built_in(name).is_some() || macros::is_quote_name(name) {
+        return Err(format!(
             "redeclaring built-in function {} is not allowed",
             name
-        )),
-        None => {
-            let declare_fn = Rc::new(Object::Function(params, body, env.clone()));
-            env.borrow_mut().set(name, declare_fn.clone());
-            Ok(declare_fn)
+        ));
+    }
+
+    Ok(())
+}
+
+fn function_object(params: Vec<Identifier>, body: BlockStatement, env: &Env) -> Rc<Object> {
+    // calling a function that yields returns a generator instead of running its body
+    if generator::contains_yield(&body.body) {
+        Rc::new(Object::GeneratorFunction(params, body, env.clone()))
+    } else {
+        Rc::new(Object::Function(params, body, env.clone()))
+    }
+}
+
+fn eval_struct_statement(struct_stmt: &Struct, env: &Env) -> Result<Rc<Object>, EvalError> {
+    let mut fields: Vec<String> = Vec::new();
+
+    for field in &struct_stmt.fields {
+        if fields.contains(&field.name) {
+            return Err(format!(
+                "field {} is declared more than once in struct {}",
+                field.name, struct_stmt.name
+            ));
+        }
+
+        fields.push(field.name.clone());
+    }
+
+    let ty = StructType::new(struct_stmt.name.name.clone(), fields);
+
+    env.borrow_mut().set(
+        struct_stmt.name.name.clone(),
+        Rc::new(Object::Struct(Rc::new(ty))),
+    )?;
+    Ok(Rc::new(Object::Null))
+}
+
+fn eval_impl_statement(impl_stmt: &Impl, env: &Env) -> Result<Rc<Object>, EvalError> {
+    let ty = match &*eval_identifier(&impl_stmt.name.name, env)? {
+        Object::Struct(ty) => Rc::clone(ty),
+        obj => {
+            return Err(format!(
+                "impl expects a struct but {} is {}",
+                impl_stmt.name,
+                obj.type_name()
+            ))
+        }
+    };
+
+    let mut methods: Vec<(String, Rc<Object>)> = impl_stmt
+        .methods
+        .iter()
+        .map(|method| {
+            let func = function_object(method.params.clone(), *method.body.clone(), env);
+            (method.name.clone(), func)
+        })
+        .collect();
+
+    let trait_type = match &impl_stmt.trait_name {
+        Some(trait_name) => {
+            let trait_type = match &*eval_identifier(&trait_name.name, env)? {
+                Object::Trait(trait_type) => Rc::clone(trait_type),
+                obj => {
+                    return Err(format!(
+                        "impl expects a trait but {} is {}",
+                        trait_name,
+                        obj.type_name()
+                    ))
+                }
+            };
+
+            methods.extend(trait_defaults(&trait_type, impl_stmt, &ty)?);
+            Some(trait_type)
+        }
+        None => None,
+    };
+
+    // nothing is added to the struct unless the whole impl is valid
+    for (i, (name, _)) in methods.iter().enumerate() {
+        if ty.method(name).is_some() || methods[..i].iter().any(|(other, _)| other == name) {
+            return Err(format!(
+                "method {} is already defined for {}",
+                name, ty.name
+            ));
+        }
+    }
+
+    ty.methods.borrow_mut().extend(methods);
+
+    if let Some(trait_type) = trait_type {
+        ty.traits.borrow_mut().push(trait_type);
+    }
+
+    Ok(Rc::new(Object::Null))
+}
+
+// Checks the methods of an impl against the trait it implements and returns
+// the default methods of the trait that the impl leaves out.
+fn trait_defaults(
+    trait_type: &Rc<TraitType>,
+    impl_stmt: &Impl,
+    ty: &StructType,
+) -> Result<Vec<(String, Rc<Object>)>, EvalError> {
+    if ty.implements(trait_type) {
+        return Err(format!(
+            "{} already implements {}",
+            ty.name, trait_type.name
+        ));
+    }
+
+    for method in &impl_stmt.methods {
+        match trait_type.arity(&method.name) {
+            None => {
+                return Err(format!(
+                    "method {} is not a member of trait {}",
+                    method.name, trait_type.name
+                ))
+            }
+            Some(arity) if arity != method.params.len() => {
+                return Err(format!(
+                    "method {} of trait {} takes {} parameters but got {}",
+                    method.name,
+                    trait_type.name,
+                    arity,
+                    method.params.len()
+                ))
+            }
+            Some(_) => {}
+        }
+    }
+
+    let is_defined = |name: &str| impl_stmt.methods.iter().any(|method| method.name == name);
+
+    if let Some((name, _)) = trait_type
+        .required
+        .iter()
+        .find(|(name, _)| !is_defined(name))
+    {
+        return Err(format!(
+            "impl {} for {} is missing the method {}",
+            trait_type.name, ty.name, name
+        ));
+    }
+
+    Ok(trait_type
+        .methods
+        .iter()
+        .filter(|(name, _)| !is_defined(name))
+        .map(|(name, method)| (name.clone(), Rc::clone(method)))
+        .collect())
+}
+
+fn eval_trait_statement(trait_stmt: &Trait, env: &Env) -> Result<Rc<Object>, EvalError> {
+    let names = trait_stmt
+        .required
+        .iter()
+        .map(|signature| &signature.name)
+        .chain(trait_stmt.methods.iter().map(|method| &method.name));
+
+    let mut declared: Vec<&String> = Vec::new();
+    for name in names {
+        if declared.contains(&name) {
+            return Err(format!(
+                "method {} is declared more than once in trait {}",
+                name, trait_stmt.name
+            ));
         }
+
+        declared.push(name);
     }
+
+    let trait_type = TraitType {
+        name: trait_stmt.name.name.clone(),
+        required: trait_stmt
+            .required
+            .iter()
+            .map(|signature| (signature.name.clone(), signature.params.len()))
+            .collect(),
+        methods: trait_stmt
+            .methods
+            .iter()
+            .map(|method| {
+                let func = function_object(method.params.clone(), *method.body.clone(), env);
+                (method.name.clone(), func)
+            })
+            .collect(),
+    };
+
+    env.borrow_mut().set(
+        trait_stmt.name.name.clone(),
+        Rc::new(Object::Trait(Rc::new(trait_type))),
+    )?;
+    Ok(Rc::new(Object::Null))
 }
 
 fn eval_return_statement(argument: &Expression, env: &Env) -> Result<Rc<Object>, EvalError> {
-    Ok(Rc::new(Object::ReturnValue(eval_expression(
+    if let Expression::FunctionCall(call) = argument {
+        if TAIL_CALLS.with(Cell::get) && !defer::has_pending() && !macros::is_quote_call(call) {
+            return eval_tail_call(call, env);
+        }
+    }
+
+    Ok(Rc::new(Object::ReturnValue(eval_operand!(
         argument.clone(),
-        env,
-    )?)))
+        env
+    ))))
+}
+
+// A call in tail position is not applied here, it is handed back to the caller
+// as a tail call object so the function that is running can return first.
+fn eval_tail_call(call: &FunctionCall, env: &Env) -> Result<Rc<Object>, EvalError> {
+    let func = eval_callee(&call.call, env)?;
+    if func.is_jump() {
+        return Ok(func);
+    }
+
+    let args = eval_expressions(&call.arguments, env)?;
+
+    let result = match &*func {
+        Object::Function(_, _, _) => Rc::new(Object::TailCall(func, args)),
+        _ => apply_function_in(&func, args, env)?,
+    };
+
+    Ok(Rc::new(Object::ReturnValue(result)))
 }
 
 fn eval_if_statement(
     condition: &Expression,
     consequent: &BlockStatement,
     alternate: &Option<Box<BlockStatement>>,
-    branches: &Vec<If>,
+    branches: &[If],
     env: &Env,
 ) -> Result<Rc<Object>, EvalError> {
-    let condition = eval_expression(condition.clone(), &Rc::clone(env))?;
+    let condition = eval_operand!(condition.clone(), &Rc::clone(env));
 
     if is_truthy(&condition) {
-        eval_block_statements(&(consequent.body), env)
+        eval_scoped_block(consequent, env)
     } else {
         for stmt in branches {
-            let condition = eval_expression(stmt.condition.clone(), env)?;
+            let condition = eval_operand!(stmt.condition.clone(), env);
 
             if is_truthy(&condition) {
-                return eval_block_statements(&stmt.consequent.body, env);
+                return eval_scoped_block(&stmt.consequent, env);
             } else {
                 continue;
             }
         }
 
         match alternate {
-            Some(alt) => eval_block_statements(&(alt.body), env),
+            Some(alt) => eval_scoped_block(alt, env),
             None => Ok(Rc::new(Object::Null)),
         }
     }
 }
 
-fn eval_expressions(exprs: &Vec<Expression>, env: &Env) -> Result<Vec<Rc<Object>>, EvalError> {
+fn eval_expressions(exprs: &[Expression], env: &Env) -> Result<Vec<Rc<Object>>, EvalError> {
     let mut list = Vec::new();
     for expr in exprs {
+        // a spread adds every element of its iterable to the list
+        if let Expression::Spread(Spread { argument, .. }) = expr {
+            let iterable = eval_expression((**argument).clone(), env)?;
+            let mut iterator = make_iterator(&iterable)?;
+
+            while let Some(item) = next_item(&mut iterator)? {
+                list.push(item);
+            }
+
+            continue;
+        }
+
         let val = eval_expression(expr.clone(), &Rc::clone(env))?;
         list.push(val);
     }
@@ -181,125 +644,657 @@ fn validate_func_args_len(params_len: usize, args_len: usize) -> Result<(), Eval
 fn eval_expression(expr: Expression, env: &Env) -> Result<Rc<Object>, EvalError> {
     match expr {
         Expression::UnaryOperator(unop) => eval_unary_operator(unop, env),
+        Expression::Assignment(assignment) => eval_assignment(assignment, env),
+        Expression::FunctionCall(call) if macros::is_quote_call(&call) => {
+            macros::eval_quote_call(call, env)
+        }
         Expression::FunctionCall(FunctionCall {
             call, arguments, ..
-        }) => match *call {
-            Expression::Identifier(Identifier { name, .. }) => {
-                // Let's distinguish the built-in funcs and declared ones
-
-                let args = eval_expressions(&arguments, env)?;
-
-                match BUILT_INS.borrow().get(name.as_str()) {
-                    Some(bfn) => Ok(bfn(args)),
-                    None => {
-                        let func = env
-                            .borrow_mut()
-                            .get(&name)
-                            .unwrap_or_else(|| panic!("{} not declared", name));
-
-                        let result = match &*func {
-                            Object::Function(params, body, env) => {
-                                let mut env = Environment::new_enclosed_environment(env);
-
-                                if let Err(e) = validate_func_args_len(params.len(), args.len()) {
-                                    return Err(e);
-                                }
-
-                                params.iter().enumerate().for_each(|(i, param)| {
-                                    env.set(param.name.clone(), args[i].clone());
-                                });
-
-                                let evaluated =
-                                    eval_block_statements(&body.body, &Rc::new(RefCell::new(env)))?;
-
-                                return unwrap_return(evaluated);
-                            }
-                            f => Err(format!("expected {} to be a function", f)),
+        }) => {
+            let func = eval_callee(&call, env)?;
+            if func.is_jump() {
+                return Ok(func);
+            }
+
+            let args = eval_expressions(&arguments, env)?;
+
+            stack::trace(apply_function_in(&func, args, env), || call.to_string())
+        }
+        Expression::MethodCall(method_call) => eval_method_call(method_call, env),
+        Expression::Array(Array { elements, .. }) => {
+            Ok(Rc::new(Object::array(eval_expressions(&elements, env)?)))
+        }
+        Expression::Tuple(Tuple { elements, .. }) => {
+            Ok(Rc::new(Object::Tuple(eval_expressions(&elements, env)?)))
+        }
+        Expression::Set(Set { elements, .. }) => {
+            set::eval_set_literal(eval_expressions(&elements, env)?)
+        }
+        Expression::Hash(Hash { entries, .. }) => {
+            let mut hash = BTreeMap::new();
+
+            for entry in entries {
+                match entry {
+                    HashEntry::Pair(key, value) => {
+                        let key = eval_operand!(key, env);
+                        let value = eval_operand!(value, env);
+                        hash.insert(HashKey::from_object(&key)?, value);
+                    }
+                    HashEntry::Spread(Spread { argument, .. }) => {
+                        let spread = eval_operand!(*argument, env);
+
+                        let Object::Hash(pairs) = &*spread else {
+                            return Err(format!(
+                                "expected a hash to spread into the hash but got {}",
+                                spread.type_name()
+                            ));
                         };
 
-                        result
+                        for (key, value) in RefCell::borrow(pairs).iter() {
+                            hash.insert(key.clone(), Rc::clone(value));
+                        }
                     }
                 }
             }
-            _ => Err(format!(
-                "expected to get function declaratin from the object store but got {:?}",
-                call
-            )),
-        },
+
+            Ok(Rc::new(Object::hash(hash)))
+        }
+        Expression::Spread(_) => {
+            Err("spread can only be used in the arguments of a call or a collection".to_string())
+        }
+        Expression::ListComprehension(list_comprehension) => {
+            let ListComprehension {
+                element,
+                comprehension,
+                ..
+            } = *list_comprehension;
+            let mut elements = Vec::new();
+
+            let result = eval_comprehension(&comprehension, env, |scope| {
+                elements.push(eval_operand!((*element).clone(), scope));
+                Ok(Rc::new(Object::Null))
+            })?;
+
+            if result.is_jump() {
+                return Ok(result);
+            }
+
+            Ok(Rc::new(Object::array(elements)))
+        }
+        Expression::HashComprehension(hash_comprehension) => {
+            let HashComprehension {
+                key,
+                value,
+                comprehension,
+                ..
+            } = *hash_comprehension;
+            let mut hash = BTreeMap::new();
+
+            let result = eval_comprehension(&comprehension, env, |scope| {
+                let key = eval_operand!((*key).clone(), scope);
+                let value = eval_operand!((*value).clone(), scope);
+                hash.insert(HashKey::from_object(&key)?, value);
+                Ok(Rc::new(Object::Null))
+            })?;
+
+            if result.is_jump() {
+                return Ok(result);
+            }
+
+            Ok(Rc::new(Object::hash(hash)))
+        }
+        Expression::Field(Field {
+            receiver, field, ..
+        }) => {
+            let receiver = eval_operand!(*receiver, env);
+            eval_field(&receiver, &field.name)
+        }
+        Expression::Index(Index { left, index, .. }) => {
+            let left = eval_operand!(*left, env);
+            let index = eval_operand!(*index, env);
+
+            if let Object::Instance(_) = &*left {
+                return eval_operator_method(&left, "index", index);
+            }
+
+            eval_index(&left, &index)
+        }
+        Expression::Slice(slice) => {
+            let left = eval_operand!(*slice.left, env);
+            let mut bounds = [None, None, None];
+
+            for (bound, expr) in bounds.iter_mut().zip([slice.start, slice.end, slice.step]) {
+                if let Some(expr) = expr {
+                    *bound = Some(eval_operand!(*expr, env));
+                }
+            }
+
+            let [start, end, step] = bounds;
+            slice::eval_slice(&left, start, end, step)
+        }
         Expression::Literal(literal) => eval_literal(&literal),
         Expression::Identifier(identifier) => eval_identifier(identifier.name.as_str(), env),
         Expression::Prefix(UnaryExpression {
             operator, operand, ..
         }) => {
-            let val = eval_expression(*operand, &Rc::clone(env))?;
+            let val = eval_operand!(*operand, &Rc::clone(env));
             eval_prefix(operator.kind, &val)
         }
         Expression::Infix(binary_expression) => {
-            let left = eval_expression(*binary_expression.left, &Rc::clone(env))?;
-            let right = eval_expression(*binary_expression.right, &Rc::clone(env))?;
+            let left = eval_operand!(*binary_expression.left, &Rc::clone(env));
+            let right = eval_operand!(*binary_expression.right, &Rc::clone(env));
+
+            if binary_expression.operator.kind == TokenKind::In {
+                return set::eval_membership(&left, &right);
+            }
+
+            if let Object::Instance(_) = &*left {
+                return eval_instance_infix(binary_expression.operator.kind, &left, right);
+            }
+
+            if let Object::Instance(_) = &*right {
+                return eval_reflected_infix(binary_expression.operator.kind, left, &right);
+            }
+
             eval_infix(binary_expression.operator, &left, &right)
         }
+        Expression::If(if_expr) => eval_if_statement(
+            &if_expr.condition,
+            &if_expr.consequent,
+            &if_expr.alternate,
+            &if_expr.branches,
+            env,
+        ),
+        Expression::Block(block) => eval_scoped_block(&block, env),
+        Expression::Conditional(Conditional {
+            condition,
+            consequent,
+            alternate,
+            ..
+        }) => {
+            let condition = eval_operand!(*condition, env);
+
+            if is_truthy(&condition) {
+                eval_expression(*consequent, env)
+            } else {
+                eval_expression(*alternate, env)
+            }
+        }
     }
 }
 
+fn eval_callee(call: &Expression, env: &Env) -> Result<Rc<Object>, EvalError> {
+    // Let's distinguish the built-in funcs and declared ones
+    match call {
+        Expression::Identifier(Identifier { name, .. }) => match built_in(name) {
+            Some(bfn) => Ok(bfn),
+            None => eval_identifier(name, env),
+        },
+        call => eval_expression(call.clone(), env),
+    }
+}
+
+// Calls the function with the scope of the call, which only eval and load use.
+fn apply_function_in(
+    func: &Object,
+    args: Vec<Rc<Object>>,
+    env: &Env,
+) -> Result<Rc<Object>, EvalError> {
+    match func {
+        Object::ScopedBuiltin(bfn) => bfn(args, env),
+        func => apply_function(func, args),
+    }
+}
+
+fn apply_function(func: &Object, args: Vec<Rc<Object>>) -> Result<Rc<Object>, EvalError> {
+    resolve_tail_calls(call_function(func, args)?)
+}
+
+// Runs the tail calls one after another in a loop, a chain of them (even
+// between mutually recursive functions) uses a constant amount of stack.
+fn resolve_tail_calls(mut result: Rc<Object>) -> Result<Rc<Object>, EvalError> {
+    while let Object::TailCall(func, args) = &*result {
+        result = call_function(func, args.clone())?;
+    }
+
+    Ok(result)
+}
+
+fn call_function(func: &Object, args: Vec<Rc<Object>>) -> Result<Rc<Object>, EvalError> {
+    match func {
+        Object::Function(params, body, env) => {
+            let env = bind_arguments(params, args, env)?;
+
+            stack::enter()?;
+
+            // the deferred actions run even when the body fails
+            defer::push_frame(vec![]);
+            let result = eval_block_statements(&body.body, &env).and_then(unwrap_return);
+            let result = defer::run_frame(defer::pop_frame(), result);
+
+            stack::leave();
+            result
+        }
+        Object::GeneratorFunction(params, body, env) => {
+            let env = bind_arguments(params, args, env)?;
+
+            Ok(Rc::new(Object::Generator(Rc::new(RefCell::new(
+                Generator::new(body.body.clone(), env),
+            )))))
+        }
+        Object::Builtin(bfn) => unwrap_builtin_error(bfn(args)),
+        Object::Struct(ty) => {
+            validate_func_args_len(ty.fields.len(), args.len())?;
+
+            Ok(Rc::new(Object::Instance(Rc::new(Instance {
+                ty: Rc::clone(ty),
+                values: RefCell::new(args),
+            }))))
+        }
+        f => Err(format!("expected {} to be a function", f)),
+    }
+}
+
+fn bind_arguments(
+    params: &[Identifier],
+    args: Vec<Rc<Object>>,
+    env: &Env,
+) -> Result<Env, EvalError> {
+    let mut env = Environment::new_enclosed_environment(env);
+
+    validate_func_args_len(params.len(), args.len())?;
+
+    for (param, arg) in params.iter().zip(args) {
+        env.set(param.name.clone(), arg)?;
+    }
+
+    Ok(Rc::new(RefCell::new(env)))
+}
+
+// Builtins report their failures as error objects, which are raised as runtime errors.
+fn unwrap_builtin_error(obj: Rc<Object>) -> Result<Rc<Object>, EvalError> {
+    match &*obj {
+        Object::Error(e) => Err(e.clone()),
+        _ => Ok(obj),
+    }
+}
+
+fn eval_method_call(method_call: MethodCall, env: &Env) -> Result<Rc<Object>, EvalError> {
+    let receiver = eval_operand!(*method_call.receiver, env);
+    let name = method_call.method.name;
+
+    let mut args = vec![Rc::clone(&receiver)];
+    args.extend(eval_expressions(&method_call.arguments, env)?);
+
+    // generators are resumed by the evaluator itself since builtins can't evaluate their body
+    if let (Object::Generator(generator), "next") = (&*receiver, name.as_str()) {
+        validate_func_args_len(0, args.len() - 1)?;
+
+        return Ok(generator::resume_generator(generator)?.unwrap_or_else(|| Rc::new(Object::Null)));
+    }
+
+    if let Some(method) = instance_method(&receiver, &name) {
+        return stack::trace(apply_function(&method, args), || {
+            format!("{}.{}", receiver.type_name(), name)
+        });
+    }
+
+    // builtin methods of the receiver's type come first, then the user functions
+    // that take the receiver as their first parameter
+    if let Some(method) = lookup_method(&receiver, &name) {
+        return unwrap_builtin_error(method(args));
+    }
+
+    let func = env.borrow_mut().get(&name);
+
+    match func {
+        Some(func) if matches!(*func, Object::Function(..)) => {
+            stack::trace(apply_function(&func, args), || name.clone())
+        }
+        _ => Err(format!(
+            "unknown method {} for type {}",
+            name,
+            receiver.type_name()
+        )),
+    }
+}
+
+fn instance_method(receiver: &Object, name: &str) -> Option<Rc<Object>> {
+    match receiver {
+        Object::Instance(instance) => instance.ty.method(name),
+        _ => None,
+    }
+}
+
+fn eval_field(receiver: &Object, name: &str) -> Result<Rc<Object>, EvalError> {
+    match receiver {
+        Object::Instance(instance) => instance
+            .field(name)
+            .ok_or_else(|| format!("type {} has no field {}", instance.ty.name, name)),
+        _ => Err(format!(
+            "field access is not supported for type {}",
+            receiver.type_name()
+        )),
+    }
+}
+
+// Instances define the operators with the methods of their impl blocks, `!=` falls
+// back to negating `eq` when there is no `ne` method, and without an `eq` method
+// an instance is only equal to itself.
+fn eval_instance_infix(
+    operator: TokenKind,
+    left: &Rc<Object>,
+    right: Rc<Object>,
+) -> Result<Rc<Object>, EvalError> {
+    // an instance without an add method is joined to a string by its text
+    if let (TokenKind::Plus, Object::String(text), None) =
+        (&operator, &*right, instance_method(left, "add"))
+    {
+        return Ok(Rc::new(Object::String(format!(
+            "{}{}",
+            left.to_text()?,
+            text
+        ))));
+    }
+
+    let name = match operator {
+        TokenKind::Plus => "add",
+        TokenKind::Minus => "sub",
+        TokenKind::Asterisk => "mul",
+        TokenKind::Slash => "div",
+        TokenKind::Equal if instance_method(left, "eq").is_none() => {
+            return Ok(Rc::new(Object::Boolean(is_same_instance(left, &right))));
+        }
+        TokenKind::Equal => "eq",
+        TokenKind::NotEqual if instance_method(left, "ne").is_some() => "ne",
+        TokenKind::NotEqual => {
+            let equal = eval_instance_infix(TokenKind::Equal, left, right)?;
+            return eval_prefix_bang(&equal);
+        }
+        TokenKind::LessThan => "lt",
+        TokenKind::LessEqual => "le",
+        TokenKind::GreaterThan => "gt",
+        TokenKind::GreaterEqual => "ge",
+        _ => {
+            return Err(format!(
+                "eval infix not available for operator: {}",
+                operator
+            ))
+        }
+    };
+
+    eval_operator_method(left, name, right)
+}
+
+fn is_same_instance(left: &Object, right: &Object) -> bool {
+    match (left, right) {
+        (Object::Instance(left), Object::Instance(right)) => Rc::ptr_eq(left, right),
+        _ => false,
+    }
+}
+
+// An instance on the right of another operand gets the operator through a reflected
+// method, so `2 * a` calls `a.rmul(2)`, while the comparisons are mirrored and
+// `2 < a` calls `a.gt(2)`.
+fn eval_reflected_infix(
+    operator: TokenKind,
+    left: Rc<Object>,
+    right: &Rc<Object>,
+) -> Result<Rc<Object>, EvalError> {
+    if let (TokenKind::Plus, Object::String(text), None) =
+        (&operator, &*left, instance_method(right, "radd"))
+    {
+        return Ok(Rc::new(Object::String(format!(
+            "{}{}",
+            text,
+            right.to_text()?
+        ))));
+    }
+
+    let name = match operator {
+        TokenKind::Plus => "radd",
+        TokenKind::Minus => "rsub",
+        TokenKind::Asterisk => "rmul",
+        TokenKind::Slash => "rdiv",
+        TokenKind::LessThan => return eval_instance_infix(TokenKind::GreaterThan, right, left),
+        TokenKind::LessEqual => return eval_instance_infix(TokenKind::GreaterEqual, right, left),
+        TokenKind::GreaterThan => return eval_instance_infix(TokenKind::LessThan, right, left),
+        TokenKind::GreaterEqual => return eval_instance_infix(TokenKind::LessEqual, right, left),
+        operator => return eval_instance_infix(operator, right, left),
+    };
+
+    eval_operator_method(right, name, left)
+}
+
+fn eval_operator_method(
+    receiver: &Rc<Object>,
+    name: &str,
+    argument: Rc<Object>,
+) -> Result<Rc<Object>, EvalError> {
+    match instance_method(receiver, name) {
+        Some(method) => stack::trace(
+            apply_function(&method, vec![Rc::clone(receiver), argument]),
+            || format!("{}.{}", receiver.type_name(), name),
+        ),
+        None => Err(format!(
+            "type {} does not define the {} method",
+            receiver.type_name(),
+            name
+        )),
+    }
+}
+
+// Registered as the display hook of the instances, so a to_string method
+// is used wherever an instance is converted to text.
+fn display_instance(instance: &Rc<Instance>) -> Option<Result<String, EvalError>> {
+    let method = instance.ty.method("to_string")?;
+    let receiver = Rc::new(Object::Instance(Rc::clone(instance)));
+
+    Some(
+        stack::trace(apply_function(&method, vec![receiver]), || {
+            format!("{}.to_string", instance.ty.name)
+        })
+        .and_then(|value| value.to_text()),
+    )
+}
+
+fn eval_index(left: &Object, index: &Object) -> Result<Rc<Object>, EvalError> {
+    match (left, index) {
+        (Object::Array(elements), Object::Integer(i)) => {
+            element_at(left, &RefCell::borrow(elements), *i)
+        }
+        (Object::Tuple(elements), Object::Integer(i)) => element_at(left, elements, *i),
+        (Object::String(value), Object::Integer(i)) => usize::try_from(*i)
+            .ok()
+            .and_then(|i| value.chars().nth(i))
+            .map(|ch| Rc::new(Object::String(ch.to_string())))
+            .ok_or_else(|| {
+                format!(
+                    "index {} is out of range for string of length {}",
+                    i,
+                    value.chars().count()
+                )
+            }),
+        (Object::Bytes(value), Object::Integer(i)) => usize::try_from(*i)
+            .ok()
+            .and_then(|i| value.get(i))
+            .map(|byte| Rc::new(Object::Integer(*byte as i64)))
+            .ok_or_else(|| {
+                format!(
+                    "index {} is out of range for bytes of length {}",
+                    i,
+                    value.len()
+                )
+            }),
+        (Object::Hash(pairs), key) => RefCell::borrow(pairs)
+            .get(&HashKey::from_object(key)?)
+            .cloned()
+            .ok_or_else(|| format!("key {} is not in the hash", key)),
+        (Object::Array(_) | Object::Tuple(_) | Object::String(_) | Object::Bytes(_), index) => Err(
+            format!("index must be an integer but got {}", index.type_name()),
+        ),
+        (left, _) => Err(format!(
+            "index operator is not supported for type {}",
+            left.type_name()
+        )),
+    }
+}
+
+fn element_at(left: &Object, elements: &[Rc<Object>], i: i64) -> Result<Rc<Object>, EvalError> {
+    usize::try_from(i)
+        .ok()
+        .and_then(|i| elements.get(i))
+        .cloned()
+        .ok_or_else(|| {
+            format!(
+                "index {} is out of range for {} of length {}",
+                i,
+                left.type_name(),
+                elements.len()
+            )
+        })
+}
+
 fn eval_unary_operator(unop: UnaryOperator, env: &Env) -> Result<Rc<Object>, EvalError> {
     let mut scope = env.borrow_mut();
     let object = scope.get(&unop.identifer.name);
 
     if let Some(var) = object {
-        match *var {
-            Object::Integer(value) => {
-                let new_value = Rc::new(Object::Integer(match unop.ty {
-                    ast::expression::UnaryOperatorType::PreIncrement => value + 1,
-                    ast::expression::UnaryOperatorType::PostIncrement => value + 1,
-                    ast::expression::UnaryOperatorType::PreDecrement => value - 1,
-                    ast::expression::UnaryOperatorType::PostDecrement => value - 1,
-                }));
-
-                match unop.ty {
-                    ast::expression::UnaryOperatorType::PreIncrement
-                    | ast::expression::UnaryOperatorType::PreDecrement => {
-                        scope.set(unop.identifer.name, new_value.clone());
-                        return Ok(new_value);
-                    }
-                    ast::expression::UnaryOperatorType::PostIncrement
-                    | ast::expression::UnaryOperatorType::PostDecrement => {
-                        let temp = var;
-                        scope.set(unop.identifer.name, new_value.clone());
-                        return Ok(temp);
-                    }
-                };
-            }
+        let delta: i64 = match unop.ty {
+            ast::expression::UnaryOperatorType::PreIncrement
+            | ast::expression::UnaryOperatorType::PostIncrement => 1,
+            ast::expression::UnaryOperatorType::PreDecrement
+            | ast::expression::UnaryOperatorType::PostDecrement => -1,
+        };
+
+        let new_value = match &*var {
+            Object::Integer(value) => match value.checked_add(delta) {
+                Some(new_value) => Object::Integer(new_value),
+                None => big_integer_object(BigInt::from(*value) + delta),
+            },
+            Object::BigInteger(value) => big_integer_object(value + delta),
             _ => {
                 return Err(format!(
                     "unary operation can only performed for number objects but got {}",
                     var
                 ))
             }
+        };
+
+        let new_value = Rc::new(new_value);
+
+        scope.assign(&unop.identifer.name, new_value.clone())?;
+
+        match unop.ty {
+            ast::expression::UnaryOperatorType::PreIncrement
+            | ast::expression::UnaryOperatorType::PreDecrement => Ok(new_value),
+            ast::expression::UnaryOperatorType::PostIncrement
+            | ast::expression::UnaryOperatorType::PostDecrement => Ok(var),
         }
     } else {
-        return Err(format!(
+        Err(format!(
             "variable {} is not initialized and can not be operated",
             unop.identifer.name
-        ));
+        ))
+    }
+}
+
+fn eval_assignment(assignment: Assignment, env: &Env) -> Result<Rc<Object>, EvalError> {
+    match *assignment.target {
+        Expression::Identifier(Identifier { name, .. }) => {
+            let val = eval_operand!(*assignment.value, env);
+            env.borrow_mut().assign(&name, Rc::clone(&val))?;
+            Ok(val)
+        }
+        Expression::Index(Index { left, index, .. }) => {
+            let left = eval_operand!(*left, env);
+            let index = eval_operand!(*index, env);
+            let val = eval_operand!(*assignment.value, env);
+            assign_index(&left, &index, Rc::clone(&val))?;
+            Ok(val)
+        }
+        Expression::Field(Field {
+            receiver, field, ..
+        }) => {
+            let receiver = eval_operand!(*receiver, env);
+            let val = eval_operand!(*assignment.value, env);
+            assign_field(&receiver, &field.name, Rc::clone(&val))?;
+            Ok(val)
+        }
+        target => Err(format!("invalid assignment target: {}", target)),
+    }
+}
+
+// Arrays, hashes and instances are changed in place, so the change is seen by
+// every variable holding them.
+fn assign_index(left: &Object, index: &Object, val: Rc<Object>) -> Result<(), EvalError> {
+    match (left, index) {
+        (Object::Array(elements), Object::Integer(i)) => {
+            let mut elements = elements.borrow_mut();
+            let len = elements.len();
+
+            match usize::try_from(*i).ok().and_then(|i| elements.get_mut(i)) {
+                Some(element) => {
+                    *element = val;
+                    Ok(())
+                }
+                None => Err(format!(
+                    "index {} is out of range for array of length {}",
+                    i, len
+                )),
+            }
+        }
+        (Object::Array(_), index) => Err(format!(
+            "index must be an integer but got {}",
+            index.type_name()
+        )),
+        (Object::Hash(pairs), key) => {
+            pairs.borrow_mut().insert(HashKey::from_object(key)?, val);
+            Ok(())
+        }
+        (left, _) => Err(format!(
+            "index assignment is not supported for type {}",
+            left.type_name()
+        )),
+    }
+}
+
+fn assign_field(receiver: &Object, name: &str, val: Rc<Object>) -> Result<(), EvalError> {
+    match receiver {
+        Object::Instance(instance) if instance.set_field(name, val) => Ok(()),
+        Object::Instance(instance) => {
+            Err(format!("type {} has no field {}", instance.ty.name, name))
+        }
+        _ => Err(format!(
+            "field assignment is not supported for type {}",
+            receiver.type_name()
+        )),
     }
 }
 
 fn eval_identifier(identifier: &str, env: &Env) -> Result<Rc<Object>, EvalError> {
     match env.borrow_mut().get(identifier) {
         Some(obj) => Ok(obj),
-        None => match BUILT_INS.borrow().get(identifier) {
-            Some(obj) => Ok(Rc::new(Object::Builtin(*obj))),
-            None => Err(format!("unknown identifier {}", identifier)),
-        },
+        None => built_in(identifier).ok_or_else(|| format!("unknown identifier {}", identifier)),
+    }
+}
+
+fn built_in(name: &str) -> Option<Rc<Object>> {
+    match BUILT_INS.borrow().get(name) {
+        Some(bfn) => Some(Rc::new(Object::Builtin(*bfn))),
+        None => source::lookup(name).map(|bfn| Rc::new(Object::ScopedBuiltin(bfn))),
     }
 }
 
 fn unwrap_return(obj: Rc<Object>) -> Result<Rc<Object>, EvalError> {
-    if let Object::ReturnValue(val) = &*obj {
-        Ok(Rc::clone(val))
-    } else {
-        Ok(obj)
+    match &*obj {
+        Object::ReturnValue(val) => Ok(Rc::clone(val)),
+        // the parser rejects them, but the code built by the macros isn't parsed
+        Object::Break(_) | Object::Continue(_) => Err(format!(
+            "{} can only be used inside of a loop",
+            obj.type_name()
+        )),
+        _ => Ok(obj),
     }
 }
 
@@ -308,11 +1303,62 @@ fn eval_variable_declaration(
     expr: Expression,
     env: &Env,
 ) -> Result<Rc<Object>, EvalError> {
-    let val = eval_expression(expr, env)?;
+    let val = eval_operand!(expr, env);
     let obj: Rc<Object> = Rc::clone(&val);
 
     if let TokenKind::Identifier { name } = &identifier.kind {
-        env.borrow_mut().set(name.clone(), obj);
+        env.borrow_mut().set(name.clone(), obj)?;
+    }
+
+    Ok(Rc::new(Object::Null))
+}
+
+fn eval_tuple_declaration(
+    declaration: &TupleDeclaration,
+    env: &Env,
+) -> Result<Rc<Object>, EvalError> {
+    let val = eval_operand!(declaration.expr.clone(), env);
+
+    let values = match &*val {
+        Object::Tuple(values) if values.len() == declaration.names.len() => values,
+        Object::Tuple(values) => {
+            return Err(format!(
+                "expected a tuple of {} values but got {}",
+                declaration.names.len(),
+                values.len()
+            ))
+        }
+        obj => {
+            return Err(format!(
+                "expected a tuple of {} values but got {}",
+                declaration.names.len(),
+                obj.type_name()
+            ))
+        }
+    };
+
+    let mut scope = env.borrow_mut();
+
+    for (name, value) in declaration.names.iter().zip(values) {
+        if declaration.constant {
+            scope.set_const(name.name.clone(), Rc::clone(value))?;
+        } else {
+            scope.set(name.name.clone(), Rc::clone(value))?;
+        }
+    }
+
+    Ok(Rc::new(Object::Null))
+}
+
+fn eval_constant_declaration(
+    identifier: &Token,
+    expr: Expression,
+    env: &Env,
+) -> Result<Rc<Object>, EvalError> {
+    let val = eval_operand!(expr, env);
+
+    if let TokenKind::Identifier { name } = &identifier.kind {
+        env.borrow_mut().set_const(name.clone(), val)?;
     }
 
     Ok(Rc::new(Object::Null))
@@ -329,25 +1375,139 @@ fn eval_infix(operator: Token, left: &Object, right: &Object) -> Result<Rc<Objec
         (Object::String(left), Object::String(right)) => {
             eval_string_infix(operator.kind, left, right)
         }
-        (Object::String(left), Object::Integer(right)) => {
-            eval_string_infix(operator.kind, left, &right.to_string())
+        (Object::BigInteger(left), Object::BigInteger(right)) => {
+            eval_big_integer_infix(operator.kind, left.clone(), right.clone())
         }
-        (Object::Integer(left), Object::String(right)) => {
-            eval_string_infix(operator.kind, &left.to_string(), right)
+        (Object::Bytes(left), Object::Bytes(right)) => eval_bytes_infix(operator.kind, left, right),
+        (Object::Set(left), Object::Set(right)) => set::eval_set_infix(
+            operator.kind,
+            &RefCell::borrow(left),
+            &RefCell::borrow(right),
+        ),
+        (Object::Integer(left), Object::BigInteger(right)) => {
+            eval_big_integer_infix(operator.kind, BigInt::from(*left), right.clone())
+        }
+        (Object::BigInteger(left), Object::Integer(right)) => {
+            eval_big_integer_infix(operator.kind, left.clone(), BigInt::from(*right))
         }
-        (Object::Boolean(left), Object::String(right)) => {
-            eval_string_infix(operator.kind, &left.to_string(), right)
+        (
+            Object::String(left),
+            right @ (Object::Integer(_)
+            | Object::BigInteger(_)
+            | Object::Decimal(_)
+            | Object::Boolean(_)),
+        ) => eval_string_infix(operator.kind, left, &right.to_string()),
+        (
+            left @ (Object::Integer(_)
+            | Object::BigInteger(_)
+            | Object::Decimal(_)
+            | Object::Boolean(_)),
+            Object::String(right),
+        ) => eval_string_infix(operator.kind, &left.to_string(), right),
+        // the integers are turned into decimals when they meet one
+        (Object::Decimal(_), _) | (_, Object::Decimal(_)) => {
+            match (decimal_operand(left), decimal_operand(right)) {
+                (Some(left), Some(right)) => eval_decimal_infix(operator.kind, &left, &right),
+                _ => eval_mismatched_infix(operator.kind),
+            }
         }
-        (Object::String(left), Object::Boolean(right)) => {
-            eval_string_infix(operator.kind, left, &right.to_string())
+        (Object::Array(left), Object::Array(right)) => eval_collection_infix(operator.kind, || {
+            elements_equal(&RefCell::borrow(left), &RefCell::borrow(right))
+        }),
+        (Object::Tuple(left), Object::Tuple(right)) => {
+            eval_collection_infix(operator.kind, || elements_equal(left, right))
         }
+        (Object::Hash(left), Object::Hash(right)) => eval_collection_infix(operator.kind, || {
+            let (left, right) = (RefCell::borrow(left), RefCell::borrow(right));
+
+            if left.len() != right.len() {
+                return Ok(false);
+            }
+
+            for (key, value) in left.iter() {
+                match right.get(key) {
+                    Some(other) if eval_equality(value, other)? => {}
+                    _ => return Ok(false),
+                }
+            }
+
+            Ok(true)
+        }),
+        _ => eval_mismatched_infix(operator.kind),
+    }
+}
+
+// Values of different types are never equal, every other operator needs operands
+// it knows.
+fn eval_mismatched_infix(operator: TokenKind) -> Result<Rc<Object>, EvalError> {
+    match operator {
+        TokenKind::Equal => Ok(Rc::new(Object::Boolean(false))),
+        TokenKind::NotEqual => Ok(Rc::new(Object::Boolean(true))),
         _ => Err(format!(
             "eval infix not available for operator: {}",
-            operator.kind
+            operator
         )),
     }
 }
 
+// Arrays, tuples and hashes only know `==` and `!=`, which compare them element by
+// element. A collection nested in itself runs into the recursion depth limit.
+fn eval_collection_infix(
+    operator: TokenKind,
+    equal: impl FnOnce() -> Result<bool, EvalError>,
+) -> Result<Rc<Object>, EvalError> {
+    let negate = match operator {
+        TokenKind::Equal => false,
+        TokenKind::NotEqual => true,
+        _ => {
+            return Err(format!(
+                "eval infix not available for operator: {}",
+                operator
+            ))
+        }
+    };
+
+    stack::enter()?;
+    let equal = equal();
+    stack::leave();
+
+    Ok(Rc::new(Object::Boolean(equal? != negate)))
+}
+
+fn elements_equal(left: &[Rc<Object>], right: &[Rc<Object>]) -> Result<bool, EvalError> {
+    if left.len() != right.len() {
+        return Ok(false);
+    }
+
+    for (left, right) in left.iter().zip(right) {
+        if !eval_equality(left, right)? {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+// `==` between two elements of a collection, which asks the instances for their
+// eq method like the operator does.
+fn eval_equality(left: &Rc<Object>, right: &Rc<Object>) -> Result<bool, EvalError> {
+    let equal = match (&**left, &**right) {
+        (Object::Array(_) | Object::Hash(_), _) if Rc::ptr_eq(left, right) => return Ok(true),
+        (Object::Instance(_), _) => eval_instance_infix(TokenKind::Equal, left, Rc::clone(right))?,
+        (_, Object::Instance(_)) => eval_reflected_infix(TokenKind::Equal, Rc::clone(left), right)?,
+        _ => eval_infix(
+            Token {
+                kind: TokenKind::Equal,
+                span: Span::new_empty_span(),
+            },
+            left,
+            right,
+        )?,
+    };
+
+    Ok(is_truthy(&equal))
+}
+
 fn eval_boolean_infix(
     operator: TokenKind,
     left: bool,
@@ -364,11 +1524,11 @@ fn eval_boolean_infix(
 
 fn eval_string_infix(
     operator: TokenKind,
-    left: &String,
-    right: &String,
+    left: &str,
+    right: &str,
 ) -> Result<Rc<Object>, EvalError> {
     let result = match operator {
-        TokenKind::Else => Object::Boolean(left == right),
+        TokenKind::Equal => Object::Boolean(left == right),
         TokenKind::NotEqual => Object::Boolean(left != right),
         TokenKind::Plus => Object::String(format!("{}{}", left, right)),
         op => return Err(format!("invalid infix {} operator for string", op)),
@@ -377,12 +1537,65 @@ fn eval_string_infix(
     Ok(Rc::from(result))
 }
 
+fn eval_bytes_infix(
+    operator: TokenKind,
+    left: &[u8],
+    right: &[u8],
+) -> Result<Rc<Object>, EvalError> {
+    let result = match operator {
+        TokenKind::Equal => Object::Boolean(left == right),
+        TokenKind::NotEqual => Object::Boolean(left != right),
+        TokenKind::Plus => Object::Bytes([left, right].concat()),
+        op => return Err(format!("invalid infix {} operator for bytes", op)),
+    };
+
+    Ok(Rc::from(result))
+}
+
 fn eval_integer_infix(operator: TokenKind, left: i64, right: i64) -> Result<Rc<Object>, EvalError> {
     let result = match operator {
-        TokenKind::Plus => Object::Integer(left + right),
-        TokenKind::Minus => Object::Integer(left - right),
-        TokenKind::Asterisk => Object::Integer(left * right),
-        TokenKind::Slash => Object::Integer(left / right),
+        TokenKind::Plus => left.checked_add(right).map(Object::Integer),
+        TokenKind::Minus => left.checked_sub(right).map(Object::Integer),
+        TokenKind::Asterisk => left.checked_mul(right).map(Object::Integer),
+        TokenKind::Slash => {
+            if right == 0 {
+                return Err("division by zero".to_string());
+            }
+
+            left.checked_div(right).map(Object::Integer)
+        }
+        TokenKind::LessThan => Some(Object::Boolean(left < right)),
+        TokenKind::GreaterThan => Some(Object::Boolean(left > right)),
+        TokenKind::LessEqual => Some(Object::Boolean(left <= right)),
+        TokenKind::GreaterEqual => Some(Object::Boolean(left >= right)),
+        TokenKind::Equal => Some(Object::Boolean(left == right)),
+        TokenKind::NotEqual => Some(Object::Boolean(left != right)),
+        op => return Err(format!("invalid infix operator {} for integer", op)),
+    };
+
+    match result {
+        Some(result) => Ok(Rc::from(result)),
+        // the result doesn't fit in 64 bits, so it's promoted to a big integer
+        None => eval_big_integer_infix(operator, BigInt::from(left), BigInt::from(right)),
+    }
+}
+
+fn eval_big_integer_infix(
+    operator: TokenKind,
+    left: BigInt,
+    right: BigInt,
+) -> Result<Rc<Object>, EvalError> {
+    let result = match operator {
+        TokenKind::Plus => big_integer_object(left + right),
+        TokenKind::Minus => big_integer_object(left - right),
+        TokenKind::Asterisk => big_integer_object(left * right),
+        TokenKind::Slash => {
+            if right.sign() == Sign::NoSign {
+                return Err("division by zero".to_string());
+            }
+
+            big_integer_object(left / right)
+        }
         TokenKind::LessThan => Object::Boolean(left < right),
         TokenKind::GreaterThan => Object::Boolean(left > right),
         TokenKind::LessEqual => Object::Boolean(left <= right),
@@ -395,11 +1608,53 @@ fn eval_integer_infix(operator: TokenKind, left: i64, right: i64) -> Result<Rc<O
     Ok(Rc::from(result))
 }
 
+fn eval_decimal_infix(
+    operator: TokenKind,
+    left: &Decimal,
+    right: &Decimal,
+) -> Result<Rc<Object>, EvalError> {
+    let result = match operator {
+        TokenKind::Plus => Object::Decimal(left.add(right)),
+        TokenKind::Minus => Object::Decimal(left.sub(right)),
+        TokenKind::Asterisk => Object::Decimal(left.mul(right)),
+        TokenKind::Slash => Object::Decimal(left.div_default(right)?),
+        TokenKind::LessThan => Object::Boolean(left < right),
+        TokenKind::GreaterThan => Object::Boolean(left > right),
+        TokenKind::LessEqual => Object::Boolean(left <= right),
+        TokenKind::GreaterEqual => Object::Boolean(left >= right),
+        TokenKind::Equal => Object::Boolean(left == right),
+        TokenKind::NotEqual => Object::Boolean(left != right),
+        op => return Err(format!("invalid infix operator {} for decimal", op)),
+    };
+
+    Ok(Rc::from(result))
+}
+
+fn decimal_operand(obj: &Object) -> Option<Decimal> {
+    match obj {
+        Object::Integer(value) => Some(Decimal::from_integer(BigInt::from(*value))),
+        Object::BigInteger(value) => Some(Decimal::from_integer(value.clone())),
+        Object::Decimal(value) => Some(value.clone()),
+        _ => None,
+    }
+}
+
+// Big integers are demoted to a regular integer as soon as they fit in 64 bits again.
+fn big_integer_object(value: BigInt) -> Object {
+    match i64::try_from(&value) {
+        Ok(value) => Object::Integer(value),
+        Err(_) => Object::BigInteger(value),
+    }
+}
+
 fn eval_literal(literal: &Literal) -> Result<Rc<Object>, EvalError> {
     let result = match literal {
         Literal::Integer(Integer { raw: i, .. }) => Rc::from(Object::Integer(*i)),
+        Literal::BigInteger(BigInteger { raw: i, .. }) => Rc::from(Object::BigInteger(i.clone())),
+        Literal::Decimal(decimal) => Rc::from(Object::Decimal(Decimal::parse(&decimal.raw)?)),
         Literal::Boolean(Boolean { raw: b, .. }) => Rc::from(Object::Boolean(*b)),
         Literal::String(StringType { raw: s, .. }) => Rc::from(Object::String(s.clone())),
+        Literal::Bytes(Bytes { raw: b, .. }) => Rc::from(Object::Bytes(b.clone())),
     };
 
     Ok(result)
@@ -422,16 +1677,17 @@ fn eval_prefix_bang(expr: &Object) -> Result<Rc<Object>, EvalError> {
 }
 
 fn eval_prefix_minus(expr: &Object) -> Result<Rc<Object>, EvalError> {
-    match *expr {
-        Object::Integer(i) => Ok(Rc::from(Object::Integer(-i))),
+    match expr {
+        Object::Integer(i) => Ok(Rc::from(match i.checked_neg() {
+            Some(i) => Object::Integer(i),
+            None => big_integer_object(-BigInt::from(*i)),
+        })),
+        Object::BigInteger(i) => Ok(Rc::from(big_integer_object(-i.clone()))),
+        Object::Decimal(d) => Ok(Rc::from(Object::Decimal(d.neg()))),
         _ => Err(format!("can't apply prefix minus operator: {}", expr)),
     }
 }
 
 fn is_truthy(obj: &Object) -> bool {
-    match obj {
-        Object::Null => false,
-        Object::Boolean(false) => false,
-        _ => true,
-    }
+    !matches!(obj, Object::Null | Object::Boolean(false))
 }
@@ -0,0 +1,303 @@
+use crate::{apply_function, check_built_in_name, eval_expression};
+use ast::{
+    expression::{
+        Array, BigInteger, Boolean, Bytes, Decimal, Expression, FunctionCall, Hash, HashEntry,
+        Integer, Literal, Set, StringType, Tuple,
+    },
+    modify::{modify_expression, modify_statement, modify_statements, Modified, Modifier},
+    program::Program,
+    statement::{Function, Statement},
+    Node,
+};
+use object::{
+    env::Env,
+    object::{EvalError, Object},
+};
+use std::{cell::Cell, collections::HashMap, rc::Rc};
+use token::Span;
+
+thread_local! {
+    static FRESH_NAMES: Cell<usize> = const { Cell::new(0) };
+}
+
+// Defines the macros of the program and replaces every call of a macro with
+// the code it returns. This runs after parsing and before the evaluation, the
+// macros are kept in the environment so the next programs can use them too.
+pub fn expand_macros(node: Node, env: &Env) -> Result<Node, EvalError> {
+    let mut expander = Expander { env };
+
+    match node {
+        Node::Program(program) => {
+            let mut body: Vec<Statement> = Vec::new();
+
+            for statement in program.body {
+                match statement {
+                    Statement::Macro(definition) => define_macro(definition, env)?,
+                    statement => body.push(statement),
+                }
+            }
+
+            Ok(Node::Program(Program {
+                body: modify_statements(body, &mut expander)?,
+                span: program.span,
+            }))
+        }
+        Node::Statement(statement) => Ok(Node::Statement(Box::new(modify_statement(
+            *statement,
+            &mut expander,
+        )?))),
+        Node::Expression(expr) => Ok(Node::Expression(modify_expression(expr, &mut expander)?)),
+    }
+}
+
+fn define_macro(definition: Function, env: &Env) -> Result<(), EvalError> {
+    check_built_in_name(&definition.name)?;
+
+    let macro_object = Object::Macro(definition.params, *definition.body, Rc::clone(env));
+
+    env.borrow_mut()
+        .set(definition.name, Rc::new(macro_object))
+        .map(|_| ())
+}
+
+struct Expander<'a> {
+    env: &'a Env,
+}
+
+impl Modifier for Expander<'_> {
+    type Error = EvalError;
+
+    fn expression(&mut self, expr: Expression) -> Result<Modified, EvalError> {
+        let Expression::FunctionCall(call) = &expr else {
+            return Ok(Modified::Descend(expr));
+        };
+
+        let Expression::Identifier(name) = &*call.call else {
+            return Ok(Modified::Descend(expr));
+        };
+
+        let macro_object = self.env.borrow().get(&name.name);
+        let Some(Object::Macro(params, body, env)) = macro_object.as_deref() else {
+            return Ok(Modified::Descend(expr));
+        };
+
+        // the arguments are passed to the macro as code, not as values
+        let args = call
+            .arguments
+            .iter()
+            .map(|arg| Rc::new(Object::Quote(Node::Expression(arg.clone()))))
+            .collect();
+
+        let function = Object::Function(params.clone(), body.clone(), Rc::clone(env));
+
+        // the expansion is expanded too, since it can call other macros
+        match &*apply_function(&function, args)? {
+            Object::Quote(Node::Expression(expansion)) => self.expression(expansion.clone()),
+            obj => Err(format!(
+                "macro {} must return a quote but returned {}",
+                name,
+                obj.type_name()
+            )),
+        }
+    }
+}
+
+fn quote_call_name(call: &FunctionCall) -> Option<&str> {
+    match &*call.call {
+        Expression::Identifier(identifier) if is_quote_name(&identifier.name) => {
+            Some(&identifier.name)
+        }
+        _ => None,
+    }
+}
+
+pub fn is_quote_name(name: &str) -> bool {
+    name == "quote" || name == "unquote"
+}
+
+// quote and unquote get their argument as code, so they are not called like functions.
+pub fn is_quote_call(call: &FunctionCall) -> bool {
+    quote_call_name(call).is_some()
+}
+
+pub fn eval_quote_call(call: FunctionCall, env: &Env) -> Result<Rc<Object>, EvalError> {
+    if quote_call_name(&call) == Some("unquote") {
+        return Err("unquote can only be used inside of quote".to_string());
+    }
+
+    let argument = single_argument("quote", call)?;
+
+    // the names declared by the quoted code are replaced with fresh ones, so the
+    // expansion of a macro can't capture or overwrite the variables around it
+    let mut declarations = Declarations {
+        names: HashMap::new(),
+    };
+    let quoted = modify_expression(argument, &mut declarations)?;
+    let quoted = modify_expression(quoted, &mut Renamer(declarations.names))?;
+
+    let quoted = modify_expression(quoted, &mut Unquoter { env })?;
+
+    Ok(Rc::new(Object::Quote(Node::Expression(quoted))))
+}
+
+fn single_argument(name: &str, call: FunctionCall) -> Result<Expression, EvalError> {
+    let count = call.arguments.len();
+
+    match <[Expression; 1]>::try_from(call.arguments) {
+        Ok([argument]) => Ok(argument),
+        Err(_) => Err(format!(
+            "wrong number of arguments for {}! wanted 1 got {}",
+            name, count
+        )),
+    }
+}
+
+fn is_unquote(expr: &Expression) -> bool {
+    matches!(expr, Expression::FunctionCall(call) if quote_call_name(call) == Some("unquote"))
+}
+
+// Collects the names declared by the quoted code, leaving out the unquoted code.
+struct Declarations {
+    names: HashMap<String, String>,
+}
+
+impl Modifier for Declarations {
+    type Error = EvalError;
+
+    fn expression(&mut self, expr: Expression) -> Result<Modified, EvalError> {
+        if is_unquote(&expr) {
+            return Ok(Modified::Replace(expr));
+        }
+
+        Ok(Modified::Descend(expr))
+    }
+
+    fn declaration(&mut self, name: String) -> String {
+        // the @ can't be written in an identifier, so a fresh name is never taken
+        self.names.entry(name.clone()).or_insert_with(|| {
+            let count = FRESH_NAMES.with(|fresh_names| fresh_names.replace(fresh_names.get() + 1));
+            format!("{}@{}", name, count)
+        });
+
+        name
+    }
+}
+
+struct Renamer(HashMap<String, String>);
+
+impl Modifier for Renamer {
+    type Error = EvalError;
+
+    fn expression(&mut self, expr: Expression) -> Result<Modified, EvalError> {
+        Ok(match expr {
+            expr if is_unquote(&expr) => Modified::Replace(expr),
+            Expression::Identifier(mut identifier) => {
+                if let Some(name) = self.0.get(&identifier.name) {
+                    identifier.name = name.clone();
+                }
+
+                Modified::Replace(Expression::Identifier(identifier))
+            }
+            Expression::UnaryOperator(mut unop) => {
+                if let Some(name) = self.0.get(&unop.identifer.name) {
+                    unop.identifer.name = name.clone();
+                }
+
+                Modified::Replace(Expression::UnaryOperator(unop))
+            }
+            expr => Modified::Descend(expr),
+        })
+    }
+
+    fn declaration(&mut self, name: String) -> String {
+        self.0.get(&name).cloned().unwrap_or(name)
+    }
+}
+
+// Replaces every unquote call with the code of the value of its argument.
+struct Unquoter<'a> {
+    env: &'a Env,
+}
+
+impl Modifier for Unquoter<'_> {
+    type Error = EvalError;
+
+    fn expression(&mut self, expr: Expression) -> Result<Modified, EvalError> {
+        match expr {
+            Expression::FunctionCall(call) if quote_call_name(&call) == Some("unquote") => {
+                let span = call.span.clone();
+                let value = eval_expression(single_argument("unquote", call)?, self.env)?;
+
+                Ok(Modified::Replace(object_to_expression(&value, &span)?))
+            }
+            expr => Ok(Modified::Descend(expr)),
+        }
+    }
+}
+
+// Turns an unquoted value back into code, a quote is spliced in as the code it holds.
+fn object_to_expression(obj: &Object, span: &Span) -> Result<Expression, EvalError> {
+    let span = span.clone();
+
+    Ok(match obj {
+        Object::Integer(raw) => Expression::Literal(Literal::Integer(Integer { raw: *raw, span })),
+        Object::BigInteger(raw) => Expression::Literal(Literal::BigInteger(BigInteger {
+            raw: raw.clone(),
+            span,
+        })),
+        Object::Decimal(value) => Expression::Literal(Literal::Decimal(Decimal {
+            raw: value.to_string(),
+            span,
+        })),
+        Object::Boolean(raw) => Expression::Literal(Literal::Boolean(Boolean { raw: *raw, span })),
+        Object::String(raw) => Expression::Literal(Literal::String(StringType {
+            raw: raw.clone(),
+            span,
+        })),
+        Object::Bytes(raw) => Expression::Literal(Literal::Bytes(Bytes {
+            raw: raw.clone(),
+            span,
+        })),
+        Object::Array(elements) => Expression::Array(Array {
+            elements: objects_to_expressions(&elements.borrow(), &span)?,
+            span,
+        }),
+        Object::Tuple(elements) => Expression::Tuple(Tuple {
+            elements: objects_to_expressions(elements, &span)?,
+            span,
+        }),
+        Object::Hash(pairs) => Expression::Hash(Hash {
+            entries: pairs
+                .borrow()
+                .iter()
+                .map(|(key, value)| {
+                    Ok(HashEntry::Pair(
+                        object_to_expression(&key.to_object(), &span)?,
+                        object_to_expression(value, &span)?,
+                    ))
+                })
+                .collect::<Result<_, EvalError>>()?,
+            span,
+        }),
+        Object::Set(elements) => Expression::Set(Set {
+            elements: elements
+                .borrow()
+                .iter()
+                .map(|element| object_to_expression(&element.to_object(), &span))
+                .collect::<Result<_, EvalError>>()?,
+            span,
+        }),
+        Object::Quote(Node::Expression(expr)) => expr.clone(),
+        obj => return Err(format!("type {} can not be unquoted", obj.type_name())),
+    })
+}
+
+fn objects_to_expressions(
+    objects: &[Rc<Object>],
+    span: &Span,
+) -> Result<Vec<Expression>, EvalError> {
+    objects
+        .iter()
+        .map(|obj| object_to_expression(obj, span))
+        .collect()
+}
@@ -0,0 +1,88 @@
+use crate::eval_equality;
+use object::{
+    hash::HashKey,
+    object::{EvalError, Object},
+};
+use std::{cell::RefCell, collections::BTreeSet, rc::Rc};
+use token::TokenKind;
+
+pub fn eval_set_literal(elements: Vec<Rc<Object>>) -> Result<Rc<Object>, EvalError> {
+    let elements = elements
+        .iter()
+        .map(|element| HashKey::from_set_element(element))
+        .collect::<Result<BTreeSet<HashKey>, EvalError>>()?;
+
+    Ok(Rc::new(Object::set(elements)))
+}
+
+// `|` is the union of two sets, `&` their intersection and `-` their difference.
+pub fn eval_set_infix(
+    operator: TokenKind,
+    left: &BTreeSet<HashKey>,
+    right: &BTreeSet<HashKey>,
+) -> Result<Rc<Object>, EvalError> {
+    let result = match operator {
+        TokenKind::Pipe => Object::set(left.union(right).cloned().collect()),
+        TokenKind::Ampersand => Object::set(left.intersection(right).cloned().collect()),
+        TokenKind::Minus => Object::set(left.difference(right).cloned().collect()),
+        TokenKind::Equal => Object::Boolean(left == right),
+        TokenKind::NotEqual => Object::Boolean(left != right),
+        TokenKind::LessEqual => Object::Boolean(left.is_subset(right)),
+        TokenKind::GreaterEqual => Object::Boolean(left.is_superset(right)),
+        op => return Err(format!("invalid infix {} operator for set", op)),
+    };
+
+    Ok(Rc::new(result))
+}
+
+// Evaluates `x in xs`, the sets and hashes look the value up by its key, the
+// arrays and tuples are searched for it and the strings and bytes for a part of them.
+pub fn eval_membership(value: &Rc<Object>, collection: &Object) -> Result<Rc<Object>, EvalError> {
+    let found = match collection {
+        Object::Set(elements) => {
+            RefCell::borrow(elements).contains(&HashKey::from_set_element(value)?)
+        }
+        Object::Hash(pairs) => RefCell::borrow(pairs).contains_key(&HashKey::from_object(value)?),
+        Object::Array(elements) => contains(&RefCell::borrow(elements), value)?,
+        Object::Tuple(elements) => contains(elements, value)?,
+        Object::Bytes(bytes) => match &**value {
+            Object::Integer(byte) => bytes.iter().any(|b| *b as i64 == *byte),
+            Object::Bytes(part) => part.is_empty() || bytes.windows(part.len()).any(|w| w == part),
+            _ => {
+                return Err(format!(
+                    "expected an integer or bytes to look up in the bytes but got {}",
+                    value.type_name()
+                ))
+            }
+        },
+        Object::String(text) => match &**value {
+            Object::String(part) => text.contains(part.as_str()),
+            _ => {
+                return Err(format!(
+                    "expected a string to look up in the string but got {}",
+                    value.type_name()
+                ))
+            }
+        },
+        _ => {
+            return Err(format!(
+                "in operator is not supported for type {}",
+                collection.type_name()
+            ))
+        }
+    };
+
+    Ok(Rc::new(Object::Boolean(found)))
+}
+
+// The arrays and tuples are searched with the equality of `==`, so 1 is found in
+// [1.0d] and an instance is found by its eq method.
+fn contains(elements: &[Rc<Object>], value: &Rc<Object>) -> Result<bool, EvalError> {
+    for element in elements {
+        if Rc::ptr_eq(element, value) || eval_equality(value, element)? {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
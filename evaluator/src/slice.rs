@@ -0,0 +1,94 @@
+use num_bigint::Sign;
+use object::object::{EvalError, Object};
+use std::rc::Rc;
+
+// Evaluates xs[start:end:step] of an array, a string or bytes, the strings are sliced
+// by their characters. A negative bound counts from the end and the bounds out
+// of range are clamped, like the slices of python.
+pub fn eval_slice(
+    left: &Object,
+    start: Option<Rc<Object>>,
+    end: Option<Rc<Object>>,
+    step: Option<Rc<Object>>,
+) -> Result<Rc<Object>, EvalError> {
+    let start = slice_bound(start)?;
+    let end = slice_bound(end)?;
+    let step = slice_bound(step)?.unwrap_or(1);
+
+    if step == 0 {
+        return Err("slice step can not be zero".to_string());
+    }
+
+    Ok(Rc::new(match left {
+        Object::Array(elements) => {
+            let elements = elements.borrow();
+
+            Object::array(
+                slice_indices(elements.len(), start, end, step)
+                    .map(|i| Rc::clone(&elements[i]))
+                    .collect(),
+            )
+        }
+        Object::String(value) => {
+            let chars: Vec<char> = value.chars().collect();
+
+            Object::String(
+                slice_indices(chars.len(), start, end, step)
+                    .map(|i| chars[i])
+                    .collect(),
+            )
+        }
+        Object::Bytes(value) => Object::Bytes(
+            slice_indices(value.len(), start, end, step)
+                .map(|i| value[i])
+                .collect(),
+        ),
+        left => {
+            return Err(format!(
+                "slice operator is not supported for type {}",
+                left.type_name()
+            ))
+        }
+    }))
+}
+
+fn slice_bound(bound: Option<Rc<Object>>) -> Result<Option<i64>, EvalError> {
+    match bound.as_deref() {
+        None => Ok(None),
+        Some(Object::Integer(value)) => Ok(Some(*value)),
+        // a bound too big for an integer is past any end, so it's clamped like one
+        Some(Object::BigInteger(value)) => Ok(Some(match value.sign() {
+            Sign::Minus => i64::MIN,
+            _ => i64::MAX,
+        })),
+        Some(obj) => Err(format!(
+            "slice indices must be integers but got {}",
+            obj.type_name()
+        )),
+    }
+}
+
+fn slice_indices(
+    len: usize,
+    start: Option<i64>,
+    end: Option<i64>,
+    step: i64,
+) -> impl Iterator<Item = usize> {
+    let len = len as i64;
+
+    // a negative step walks from the end, so the bounds are clamped to the
+    // positions right before the first element instead
+    let (lower, upper) = if step > 0 { (0, len) } else { (-1, len - 1) };
+
+    let clamp = |bound: i64| {
+        let bound = if bound < 0 { bound + len } else { bound };
+        bound.clamp(lower, upper)
+    };
+
+    let start = start.map_or(if step > 0 { lower } else { upper }, clamp);
+    let end = end.map_or(if step > 0 { upper } else { lower }, clamp);
+
+    std::iter::successors(Some(start), move |i| i.checked_add(step))
+        .take_while(move |&i| if step > 0 { i < end } else { i > end })
+        .map(|i| i as usize)
+}
@@ -0,0 +1,84 @@
+use crate::{eval, expand_macros};
+use object::{
+    env::{Env, Environment},
+    object::{EvalError, Object, ScopedBuiltinFunc},
+};
+use parser::Parser;
+use std::{cell::RefCell, fs, rc::Rc};
+
+// Parses the source and evaluates it in the environment, the parse errors are
+// returned like the runtime errors.
+pub fn eval_source(source: &str, env: &Env) -> Result<Rc<Object>, EvalError> {
+    let node = Parser::parse(source.to_string())
+        .map_err(|errors| format!("parse error: {}", errors.join(", ")))?;
+
+    eval(expand_macros(node, env)?, env)
+}
+
+// eval and load need the scope of their caller, so they are kept by the evaluator
+// instead of with the builtins which only get the values of their arguments.
+pub fn lookup(name: &str) -> Option<ScopedBuiltinFunc> {
+    match name {
+        "eval" => Some(eval_builtin),
+        "load" => Some(load_builtin),
+        _ => None,
+    }
+}
+
+// eval(source) and load(path) run the code in a fresh environment, or in the
+// scope of the caller when the second argument is true.
+fn eval_builtin(args: Vec<Rc<Object>>, env: &Env) -> Result<Rc<Object>, EvalError> {
+    let (source, scope) = source_arguments("eval", &args, env)?;
+
+    eval_source(source, &scope)
+}
+
+fn load_builtin(args: Vec<Rc<Object>>, env: &Env) -> Result<Rc<Object>, EvalError> {
+    let (path, scope) = source_arguments("load", &args, env)?;
+    let source = fs::read_to_string(path).map_err(|e| format!("could not load {}: {}", path, e))?;
+
+    eval_source(&source, &scope)
+}
+
+fn source_arguments<'a>(
+    name: &str,
+    args: &'a [Rc<Object>],
+    env: &Env,
+) -> Result<(&'a str, Env), EvalError> {
+    let (argument, shared) = match args {
+        [argument] => (argument, false),
+        [argument, shared] => match &**shared {
+            Object::Boolean(shared) => (argument, *shared),
+            obj => {
+                return Err(format!(
+                    "expected a boolean as argument 1 of {} but got {}",
+                    name,
+                    obj.type_name()
+                ))
+            }
+        },
+        _ => {
+            return Err(format!(
+                "wrong number of arguments for {}! wanted 1 or 2 got {}",
+                name,
+                args.len()
+            ))
+        }
+    };
+
+    let Object::String(argument) = &**argument else {
+        return Err(format!(
+            "expected a string as argument 0 of {} but got {}",
+            name,
+            argument.type_name()
+        ));
+    };
+
+    let scope = if shared {
+        Rc::clone(env)
+    } else {
+        Rc::new(RefCell::new(Environment::default()))
+    };
+
+    Ok((argument, scope))
+}
@@ -0,0 +1,89 @@
+use object::object::EvalError;
+use std::{cell::Cell, thread};
+
+const STACK_OVERFLOW: &str = "maximum recursion depth exceeded";
+
+// How many of the innermost calls are listed by the stack overflow error.
+const TRACED_FRAMES: usize = 5;
+
+// The stack the interpreter runs on, a call of a user function takes a few
+// dozen kilobytes of it in a debug build.
+pub const STACK_SIZE: usize = 512 * 1024 * 1024;
+
+// The part of the stack the calls leave for the evaluation between two calls.
+const STACK_RESERVE: usize = 1024 * 1024;
+
+thread_local! {
+    static MAX_DEPTH: Cell<usize> = const { Cell::new(1000) };
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+    // how much of the stack the calls can use, and where the outermost one started
+    static STACK_LIMIT: Cell<usize> = const { Cell::new(usize::MAX) };
+    static STACK_BASE: Cell<usize> = const { Cell::new(0) };
+}
+
+// Every call of a user function is a frame on the native stack of the evaluator,
+// the limit turns a runaway recursion into an error before that stack overflows.
+// A depth the stack can't hold is cut short by the stack limit of the thread.
+pub fn set_max_call_depth(depth: usize) {
+    MAX_DEPTH.set(depth);
+}
+
+// Runs the function on a thread with a stack of the given size, where the
+// calls stop with the recursion error before they use up the stack, whatever
+// the maximum call depth is.
+pub fn run_with_stack<T, F>(size: usize, function: F) -> thread::Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    thread::Builder::new()
+        .stack_size(size)
+        .spawn(move || {
+            STACK_LIMIT.set(size.saturating_sub(STACK_RESERVE));
+            function()
+        })
+        .expect("failed to spawn the evaluator thread")
+        .join()
+}
+
+pub fn enter() -> Result<(), EvalError> {
+    let depth = DEPTH.get();
+    let address = stack_address();
+
+    if depth == 0 {
+        STACK_BASE.set(address);
+    }
+
+    if depth >= MAX_DEPTH.get() || STACK_BASE.get().abs_diff(address) >= STACK_LIMIT.get() {
+        return Err(STACK_OVERFLOW.to_string());
+    }
+
+    DEPTH.set(depth + 1);
+    Ok(())
+}
+
+// An address in the current frame, the distance between two of them is the
+// stack used by the frames in between.
+fn stack_address() -> usize {
+    let marker = 0u8;
+    std::ptr::addr_of!(marker) as usize
+}
+
+pub fn leave() {
+    DEPTH.set(DEPTH.get().saturating_sub(1));
+}
+
+// Lists the callee in a stack overflow error that is unwinding through its call,
+// the name is only built for the few frames that end up in the message.
+pub fn trace<T>(
+    result: Result<T, EvalError>,
+    callee: impl FnOnce() -> String,
+) -> Result<T, EvalError> {
+    result.map_err(|mut e| {
+        if e.starts_with(STACK_OVERFLOW) && e.lines().count() <= TRACED_FRAMES {
+            e.push_str(&format!("\n    in {}", callee()));
+        }
+
+        e
+    })
+}
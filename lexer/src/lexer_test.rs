@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod tests {
     use crate::Lexer;
+    use num_bigint::BigInt;
     use token::{Span, TokenKind};
 
     fn assert_tokens(
@@ -10,8 +11,7 @@ mod tests {
     ) {
         let lexer = Lexer::new(input.to_string());
 
-        let mut i: usize = 0;
-        for token in lexer {
+        for (i, token) in lexer.enumerate() {
             println!("{:?}", token);
 
             if let Some(list) = expected_tokens {
@@ -22,8 +22,6 @@ mod tests {
                 assert_eq!(token.span.start, list[i].start);
                 assert_eq!(token.span.end, list[i].end);
             }
-
-            i += 1;
         }
     }
 
@@ -100,10 +98,22 @@ mod tests {
         assert_tokens("// Sample comments", None, None);
     }
 
+    #[test]
+    fn test_doc_comments() {
+        assert_tokens(
+            "/// Adds two numbers.\n//// not a doc\nfn",
+            Some(&vec![
+                TokenKind::DocComment("Adds two numbers.".to_string()),
+                TokenKind::Function,
+            ]),
+            None,
+        );
+    }
+
     #[test]
     fn test_symbols() {
         assert_tokens(
-            "() {} , # |",
+            "() {} , # | : ? . ...",
             Some(&vec![
                 TokenKind::LeftParen,
                 TokenKind::RightParen,
@@ -112,6 +122,10 @@ mod tests {
                 TokenKind::Comma,
                 TokenKind::Hashtag,
                 TokenKind::Pipe,
+                TokenKind::Colon,
+                TokenKind::Question,
+                TokenKind::Dot,
+                TokenKind::Ellipsis,
             ]),
             None,
         );
@@ -133,7 +147,7 @@ mod tests {
     #[test]
     fn test_keywords() {
         assert_tokens(
-            "fn match if else ret for break continue",
+            "fn match if else ret for break continue const yield in struct impl defer trait macro",
             Some(&vec![
                 TokenKind::Function,
                 TokenKind::Match,
@@ -143,6 +157,14 @@ mod tests {
                 TokenKind::For,
                 TokenKind::Break,
                 TokenKind::Continue,
+                TokenKind::Const,
+                TokenKind::Yield,
+                TokenKind::In,
+                TokenKind::Struct,
+                TokenKind::Impl,
+                TokenKind::Defer,
+                TokenKind::Trait,
+                TokenKind::Macro,
             ]),
             None,
         );
@@ -205,6 +227,107 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_read_big_integer() {
+        assert_tokens(
+            "9223372036854775807 9223372036854775808",
+            Some(&vec![
+                TokenKind::Integer(i64::MAX),
+                TokenKind::BigInteger("9223372036854775808".parse::<BigInt>().unwrap()),
+            ]),
+            None,
+        );
+    }
+
+    #[test]
+    fn test_integer_literal_bases() {
+        assert_tokens(
+            "0xFF 0o755 0b1010 1_000_000 0x_ff_ff 0xffffffffffffffff",
+            Some(&vec![
+                TokenKind::Integer(255),
+                TokenKind::Integer(493),
+                TokenKind::Integer(10),
+                TokenKind::Integer(1_000_000),
+                TokenKind::Integer(0xffff),
+                TokenKind::BigInteger("18446744073709551615".parse::<BigInt>().unwrap()),
+            ]),
+            None,
+        );
+
+        for (input, error) in [
+            ("0b102", "invalid digit '2' in the integer literal 0b102"),
+            ("0o8", "invalid digit '8' in the integer literal 0o8"),
+            ("0xfg", "invalid digit 'g' in the integer literal 0xfg"),
+            ("0x", "expected digits after 0x in the integer literal"),
+            (
+                "1__000",
+                "misplaced digit separator in the integer literal 1__000",
+            ),
+            ("1_", "misplaced digit separator in the integer literal 1_"),
+        ] {
+            let mut lexer = Lexer::new(input.to_string());
+            assert_eq!(lexer.next_token().unwrap_err(), error);
+        }
+    }
+
+    #[test]
+    fn test_decimal_literals() {
+        assert_tokens(
+            "12.34d 7d 1_000.50d 3.d x.0",
+            Some(&vec![
+                TokenKind::Decimal("12.34".to_string()),
+                TokenKind::Decimal("7".to_string()),
+                TokenKind::Decimal("1000.50".to_string()),
+                TokenKind::Integer(3),
+                TokenKind::Dot,
+                TokenKind::Identifier {
+                    name: "d".to_string(),
+                },
+                TokenKind::Identifier {
+                    name: "x".to_string(),
+                },
+                TokenKind::Dot,
+                TokenKind::Integer(0),
+            ]),
+            None,
+        );
+
+        let mut lexer = Lexer::new("1.5_d".to_string());
+        assert_eq!(
+            lexer.next_token().unwrap_err(),
+            "misplaced digit separator in the decimal literal 1.5_d"
+        );
+    }
+
+    #[test]
+    fn test_bytes_literals() {
+        assert_tokens(
+            "b\"ab\\x00\\xFF\\n\\\"\" bytes",
+            Some(&vec![
+                TokenKind::Bytes(vec![b'a', b'b', 0, 255, b'\n', b'"']),
+                TokenKind::Identifier {
+                    name: "bytes".to_string(),
+                },
+            ]),
+            Some(&vec![
+                Span { start: 0, end: 16 },
+                Span { start: 18, end: 22 },
+            ]),
+        );
+
+        for (input, error) in [
+            ("b\"\\x4\"", "invalid escape \\x4\" in the bytes literal"),
+            ("b\"\\q\"", "unknown escape \\q in the bytes literal"),
+            (
+                "b\"abc",
+                "expected closing bytes with double quotation but got nothing",
+            ),
+        ] {
+            let mut lexer = Lexer::new(input.to_string());
+            assert_eq!(lexer.next_token().unwrap_err(), error);
+        }
+    }
+
     #[test]
     fn test_spans() {
         assert_tokens(
@@ -230,6 +353,73 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_unicode_spans() {
+        // spans are byte offsets and the end is the offset of the last byte
+        assert_tokens(
+            "  #نام = \"سلام 👋 世界\";",
+            Some(&vec![
+                TokenKind::Hashtag,
+                TokenKind::Identifier {
+                    name: "نام".to_string(),
+                },
+                TokenKind::Assign,
+                TokenKind::String("سلام 👋 世界".to_string()),
+                TokenKind::Semicolon,
+            ]),
+            Some(&vec![
+                Span { start: 2, end: 2 },
+                Span { start: 3, end: 8 },
+                Span { start: 10, end: 10 },
+                Span { start: 12, end: 33 },
+                Span { start: 34, end: 34 },
+            ]),
+        );
+    }
+
+    #[test]
+    fn test_unicode_identifiers() {
+        assert_tokens(
+            "_private 変数 x١ café",
+            Some(&vec![
+                TokenKind::Identifier {
+                    name: "_private".to_string(),
+                },
+                TokenKind::Identifier {
+                    name: "変数".to_string(),
+                },
+                TokenKind::Identifier {
+                    name: "x١".to_string(),
+                },
+                TokenKind::Identifier {
+                    name: "café".to_string(),
+                },
+            ]),
+            None,
+        );
+
+        // the decomposed and the precomposed forms are the same identifier
+        assert_tokens(
+            "cafe\u{301}",
+            Some(&vec![TokenKind::Identifier {
+                name: "caf\u{e9}".to_string(),
+            }]),
+            None,
+        );
+
+        let mut lexer = Lexer::new("١x".to_string());
+        assert_eq!(
+            lexer.next_token().unwrap_err(),
+            "Illegal character detected '١'"
+        );
+
+        let mut lexer = Lexer::new("👋".to_string());
+        assert_eq!(
+            lexer.next_token().unwrap_err(),
+            "Illegal character detected '👋'"
+        );
+    }
+
     #[test]
     fn test_variable_declaration() {
         assert_tokens(
@@ -1,5 +1,8 @@
+use num_bigint::BigInt;
 use std::fmt::{self, Debug};
 use token::{Span, Token, TokenKind};
+use unicode_ident::{is_xid_continue, is_xid_start};
+use unicode_normalization::UnicodeNormalization;
 
 mod lexer_test;
 
@@ -14,7 +17,7 @@ pub struct Lexer {
 impl Lexer {
     pub fn new(input: String) -> Self {
         let mut lexer = Self {
-            input: input.trim().to_string(),
+            input,
             pos: 0,      // points to current position
             next_pos: 0, // points to next position
             ch: ' ',
@@ -26,36 +29,39 @@ impl Lexer {
     }
 
     fn peek_char(&self) -> char {
-        if self.next_pos >= self.input.len() {
-            ' '
-        } else {
-            self.input
-                .chars()
-                .nth(self.next_pos)
-                .expect("Failed to read the char with peeked position")
-        }
+        self.input[self.next_pos..].chars().next().unwrap_or(' ')
     }
 
     // The most significant section of the lexer is this method
     // that reads the char with next_pos and returns it.
+    // Both of the positions are byte offsets into the input, so they
+    // move forward by the utf-8 length of the char.
     fn read_char(&mut self) {
-        if self.next_pos >= self.input.len() {
-            self.ch = ' ';
-        } else {
-            self.ch = self
-                .input
-                .chars()
-                .nth(self.next_pos)
-                .expect("Failed to read the char with current position");
-        }
-
         self.pos = self.next_pos;
-        self.next_pos += 1;
+
+        match self.input[self.pos..].chars().next() {
+            Some(ch) => {
+                self.ch = ch;
+                self.next_pos += ch.len_utf8();
+            }
+            None => self.ch = ' ',
+        }
     }
 
     pub fn next_token(&mut self) -> Result<Token, String> {
-        self.skip_whitespace();
-        self.skip_comments();
+        loop {
+            self.skip_whitespace();
+
+            if self.is_doc_comment() {
+                return Ok(self.read_doc_comment());
+            }
+
+            if !self.is_comment() {
+                break;
+            }
+
+            self.skip_comments();
+        }
 
         if self.is_eof() {
             return Ok(Token {
@@ -123,6 +129,22 @@ impl Lexer {
             '[' => TokenKind::LeftBracket,
             ']' => TokenKind::RightBracket,
             ',' => TokenKind::Comma,
+            '.' => {
+                if self.input[self.next_pos..].starts_with("..") {
+                    self.read_char();
+                    self.read_char();
+                    self.read_char();
+                    return Ok(Token {
+                        kind: TokenKind::Ellipsis,
+                        span: Span {
+                            start: self.pos - 3,
+                            end: self.pos - 1,
+                        },
+                    });
+                }
+
+                TokenKind::Dot
+            }
             '#' => TokenKind::Hashtag,
             '"' => {
                 let (start, end, content) = self.read_string()?;
@@ -264,11 +286,21 @@ impl Lexer {
                 }
             }
             ';' => TokenKind::Semicolon,
+            ':' => TokenKind::Colon,
+            '?' => TokenKind::Question,
             _ => {
                 // Reading identifiers and integers is happening here
                 let start = self.pos;
 
-                if self.ch.is_alphabetic() {
+                if self.ch == 'b' && self.peek_char() == '"' {
+                    return Ok(Token {
+                        kind: TokenKind::Bytes(self.read_bytes()?),
+                        span: Span {
+                            start,
+                            end: self.pos - 1,
+                        },
+                    });
+                } else if Self::is_identifier_start(self.ch) {
                     return Ok(Token {
                         kind: self.read_identifider(),
                         span: Span {
@@ -278,7 +310,7 @@ impl Lexer {
                     });
                 } else if self.is_numeric(self.ch) {
                     return Ok(Token {
-                        kind: self.read_integer(),
+                        kind: self.read_integer()?,
                         span: Span {
                             start,
                             end: self.pos - 1,
@@ -326,40 +358,207 @@ impl Lexer {
             self.read_char();
         }
 
-        let end = self.pos;
+        // the span includes both of the double quotes
+        let end = self.pos - 1;
 
         Ok((start - 1, end, content))
     }
 
+    // Reads a b"..." literal, the bytes that are not text are written with
+    // escapes like \x00.
+    fn read_bytes(&mut self) -> Result<Vec<u8>, String> {
+        let mut bytes = vec![];
+
+        self.read_char(); // consume the b prefix
+
+        loop {
+            self.read_char();
+
+            if self.is_eof() {
+                return Err(
+                    "expected closing bytes with double quotation but got nothing".to_string(),
+                );
+            }
+
+            match self.ch {
+                '"' => break,
+                '\\' => {
+                    self.read_char(); // consume the backslash
+                    bytes.push(self.read_byte_escape()?);
+                }
+                ch => bytes.extend_from_slice(ch.encode_utf8(&mut [0; 4]).as_bytes()),
+            }
+        }
+
+        self.read_char(); // consume the ending double quote
+
+        Ok(bytes)
+    }
+
+    fn read_byte_escape(&mut self) -> Result<u8, String> {
+        match self.ch {
+            'n' => Ok(b'\n'),
+            't' => Ok(b'\t'),
+            'r' => Ok(b'\r'),
+            '0' => Ok(b'\0'),
+            '\\' => Ok(b'\\'),
+            '"' => Ok(b'"'),
+            '\'' => Ok(b'\''),
+            'x' => {
+                let digits: String = self.input[self.next_pos..].chars().take(2).collect();
+
+                if digits.len() != 2 || !digits.chars().all(|ch| ch.is_ascii_hexdigit()) {
+                    return Err(format!("invalid escape \\x{} in the bytes literal", digits));
+                }
+
+                self.read_char();
+                self.read_char();
+
+                Ok(u8::from_str_radix(&digits, 16).unwrap_or_default())
+            }
+            ch => Err(format!("unknown escape \\{} in the bytes literal", ch)),
+        }
+    }
+
     fn read_identifider(&mut self) -> TokenKind {
         let start = self.pos;
 
-        while self.ch.is_alphanumeric() || self.ch == '_' {
+        while Self::is_identifier_continue(self.ch) {
             self.read_char();
         }
 
         let end = self.pos;
 
-        let identifier = self.input[start..end].to_string();
+        // identifiers are compared in their normalized form, so the different
+        // encodings of the same text name the same thing
+        let identifier = self.input[start..end].nfc().collect::<String>();
 
         self.lookup_identifier(identifier)
     }
 
-    fn read_integer(&mut self) -> TokenKind {
+    // Integers are decimal unless they start with 0x, 0o or 0b, and the digits
+    // can be grouped with underscores like 1_000_000.
+    fn read_integer(&mut self) -> Result<TokenKind, String> {
         let start = self.pos;
 
-        while self.is_numeric(self.ch) {
+        let radix = match (self.ch, self.peek_char()) {
+            ('0', 'x') => 16,
+            ('0', 'o') => 8,
+            ('0', 'b') => 2,
+            _ => 10,
+        };
+
+        if radix != 10 {
+            // consume the prefix
+            self.read_char();
             self.read_char();
         }
 
-        let end = self.pos;
+        let digits_start = self.pos;
+
+        // the letters are read too when there is a prefix, so 0b12 or 0xFG are
+        // reported instead of being split into an integer and an identifier
+        while self.ch == '_'
+            || self.is_numeric(self.ch)
+            || (radix != 10 && self.ch.is_ascii_alphanumeric())
+        {
+            self.read_char();
+        }
+
+        if radix == 10 {
+            if let Some(length) = self.decimal_suffix_len() {
+                return self.read_decimal(start, length);
+            }
+        }
+
+        let literal = &self.input[start..self.pos];
+        let digits = &self.input[digits_start..self.pos];
+
+        if digits.is_empty() {
+            return Err(format!(
+                "expected digits after {} in the integer literal",
+                literal
+            ));
+        }
+
+        // a separator can follow the prefix but it can't end the literal or repeat
+        if digits.ends_with('_') || digits.contains("__") {
+            return Err(format!(
+                "misplaced digit separator in the integer literal {}",
+                literal
+            ));
+        }
+
+        if let Some(ch) = digits.chars().find(|ch| *ch != '_' && !ch.is_digit(radix)) {
+            return Err(format!(
+                "invalid digit '{}' in the integer literal {}",
+                ch, literal
+            ));
+        }
+
+        let digits: String = digits.chars().filter(|ch| *ch != '_').collect();
+
+        // literals that don't fit in 64 bits are promoted to a big integer
+        match i64::from_str_radix(&digits, radix) {
+            Ok(value) => Ok(TokenKind::Integer(value)),
+            Err(_) => BigInt::parse_bytes(digits.as_bytes(), radix)
+                .map(TokenKind::BigInteger)
+                .ok_or_else(|| format!("integer literal {} is out of range", literal)),
+        }
+    }
+
+    // The length of the `.34d` or `d` that makes the digits that were read a
+    // decimal literal like 12.34d or 12d.
+    fn decimal_suffix_len(&self) -> Option<usize> {
+        let rest = &self.input[self.pos..];
+
+        let fraction = match rest.strip_prefix('.') {
+            Some(fraction) if fraction.starts_with(|ch: char| ch.is_ascii_digit()) => fraction,
+            Some(_) => return None,
+            None => rest,
+        };
+
+        let suffix = fraction.trim_start_matches(|ch: char| ch == '_' || ch.is_ascii_digit());
+        let mut chars = suffix.chars();
+
+        if chars.next() != Some('d') || chars.next().is_some_and(Self::is_identifier_continue) {
+            return None;
+        }
+
+        Some(rest.len() - suffix.len() + 1)
+    }
 
-        let identifier: i64 = self.input[start..end]
-            .to_string()
-            .parse()
-            .expect("expected identifier to be a number but it's something unknown");
+    fn read_decimal(&mut self, start: usize, suffix_len: usize) -> Result<TokenKind, String> {
+        let end = self.pos + suffix_len;
 
-        TokenKind::Integer(identifier)
+        while self.pos < end {
+            self.read_char();
+        }
+
+        let literal = &self.input[start..end];
+        let number = &literal[..literal.len() - 1]; // without the d suffix
+
+        if number
+            .split('.')
+            .any(|part| part.ends_with('_') || part.contains("__"))
+        {
+            return Err(format!(
+                "misplaced digit separator in the decimal literal {}",
+                literal
+            ));
+        }
+
+        Ok(TokenKind::Decimal(number.replace('_', "")))
+    }
+
+    // Identifiers follow the default identifier syntax of UAX #31,
+    // plus the underscore which can start an identifier too.
+    fn is_identifier_start(ch: char) -> bool {
+        ch == '_' || is_xid_start(ch)
+    }
+
+    fn is_identifier_continue(ch: char) -> bool {
+        is_xid_continue(ch)
     }
 
     fn is_numeric(&self, ch: char) -> bool {
@@ -384,8 +583,49 @@ impl Lexer {
         }
     }
 
+    // Three slashes start a doc comment, while four or more are still a regular comment.
+    fn is_doc_comment(&self) -> bool {
+        let slashes = self.input[self.pos..]
+            .chars()
+            .take(4)
+            .take_while(|ch| *ch == '/')
+            .count();
+
+        slashes == 3
+    }
+
+    fn read_doc_comment(&mut self) -> Token {
+        let start = self.pos;
+
+        self.read_char();
+        self.read_char();
+        self.read_char(); // consume triple slash
+
+        let mut doc = String::new();
+        while !self.is_eof() && self.ch != '\n' {
+            doc.push(self.ch);
+            self.read_char();
+        }
+
+        let end = self.pos - 1;
+
+        if self.ch == '\n' {
+            // consume the new line char
+            self.read_char();
+        }
+
+        Token {
+            kind: TokenKind::DocComment(doc.strip_prefix(' ').unwrap_or(&doc).to_string()),
+            span: Span { start, end },
+        }
+    }
+
+    fn is_comment(&self) -> bool {
+        self.ch == '/' && self.peek_char() == '/'
+    }
+
     fn skip_comments(&mut self) {
-        if self.ch == '/' && self.peek_char() == '/' {
+        if self.is_comment() {
             self.read_char();
             self.read_char(); // consume double slash
 
@@ -414,6 +654,14 @@ impl Lexer {
             "for" => TokenKind::For,
             "break" => TokenKind::Break,
             "continue" => TokenKind::Continue,
+            "const" => TokenKind::Const,
+            "yield" => TokenKind::Yield,
+            "in" => TokenKind::In,
+            "struct" => TokenKind::Struct,
+            "impl" => TokenKind::Impl,
+            "defer" => TokenKind::Defer,
+            "trait" => TokenKind::Trait,
+            "macro" => TokenKind::Macro,
             "true" => TokenKind::True,
             "false" => TokenKind::False,
             _ => TokenKind::Identifier {
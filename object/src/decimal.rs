@@ -0,0 +1,237 @@
+use crate::object::EvalError;
+use core::fmt;
+use num_bigint::{BigInt, Sign};
+use std::cmp::Ordering;
+
+// A division without an explicit scale is carried to this many places.
+pub const DIVISION_SCALE: u32 = 28;
+
+// round and div are asked for at most this many places, every place is another
+// digit to compute.
+pub const MAX_SCALE: u32 = 1000;
+
+// A decimal keeps its digits as an integer and the count of them that come
+// after the point, so 12.34 is 1234 with a scale of 2 and no digit is ever
+// rounded unless a division or a round asks for it.
+#[derive(Debug, Clone)]
+pub struct Decimal {
+    pub digits: BigInt,
+    pub scale: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Rounding {
+    HalfEven,
+    HalfUp,
+    HalfDown,
+    Up,
+    Down,
+    Ceiling,
+    Floor,
+}
+
+impl Rounding {
+    pub fn from_name(name: &str) -> Result<Self, EvalError> {
+        match name {
+            "half_even" => Ok(Rounding::HalfEven),
+            "half_up" => Ok(Rounding::HalfUp),
+            "half_down" => Ok(Rounding::HalfDown),
+            "up" => Ok(Rounding::Up),
+            "down" => Ok(Rounding::Down),
+            "ceiling" => Ok(Rounding::Ceiling),
+            "floor" => Ok(Rounding::Floor),
+            _ => Err(format!(
+                "unknown rounding mode {}, expected half_even, half_up, half_down, up, down, ceiling or floor",
+                name
+            )),
+        }
+    }
+}
+
+impl Decimal {
+    pub fn new(digits: BigInt, scale: u32) -> Self {
+        Decimal { digits, scale }
+    }
+
+    pub fn from_integer(value: BigInt) -> Self {
+        Decimal::new(value, 0)
+    }
+
+    // Parses the decimals written like 12.34, -0.5 or 7.
+    pub fn parse(text: &str) -> Result<Self, EvalError> {
+        let invalid = || format!("invalid decimal {}", text);
+
+        let (integer, fraction) = text.split_once('.').unwrap_or((text, ""));
+        let unsigned = integer.strip_prefix(['-', '+']).unwrap_or(integer);
+
+        if unsigned.is_empty()
+            || text.ends_with('.')
+            || !unsigned
+                .chars()
+                .chain(fraction.chars())
+                .all(|ch| ch.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+
+        let scale = u32::try_from(fraction.len()).map_err(|_| invalid())?;
+        let digits = format!("{}{}", integer, fraction)
+            .parse::<BigInt>()
+            .map_err(|_| invalid())?;
+
+        Ok(Decimal::new(digits, scale))
+    }
+
+    pub fn add(&self, other: &Decimal) -> Decimal {
+        let scale = self.scale.max(other.scale);
+        Decimal::new(self.rescale(scale) + other.rescale(scale), scale)
+    }
+
+    pub fn sub(&self, other: &Decimal) -> Decimal {
+        let scale = self.scale.max(other.scale);
+        Decimal::new(self.rescale(scale) - other.rescale(scale), scale)
+    }
+
+    pub fn mul(&self, other: &Decimal) -> Decimal {
+        Decimal::new(&self.digits * &other.digits, self.scale + other.scale)
+    }
+
+    pub fn neg(&self) -> Decimal {
+        Decimal::new(-&self.digits, self.scale)
+    }
+
+    // Divides to the given count of places after the point, the digits past
+    // them are rounded with the rounding mode.
+    pub fn div(
+        &self,
+        other: &Decimal,
+        scale: u32,
+        rounding: Rounding,
+    ) -> Result<Decimal, EvalError> {
+        if other.digits.sign() == Sign::NoSign {
+            return Err("division by zero".to_string());
+        }
+
+        let numerator = &self.digits * pow10(other.scale + scale);
+        let denominator = &other.digits * pow10(self.scale);
+
+        Ok(Decimal::new(
+            divide_rounded(&numerator, &denominator, rounding),
+            scale,
+        ))
+    }
+
+    // The division of the `/` operator, the zeros it leaves at the end are
+    // dropped down to the scale of its operands.
+    pub fn div_default(&self, other: &Decimal) -> Result<Decimal, EvalError> {
+        let scale = self.scale.max(other.scale);
+        let quotient = self.div(other, DIVISION_SCALE.max(scale), Rounding::HalfEven)?;
+
+        Ok(quotient.trim_zeros(scale))
+    }
+
+    pub fn round(&self, scale: u32, rounding: Rounding) -> Decimal {
+        if scale >= self.scale {
+            return Decimal::new(self.rescale(scale), scale);
+        }
+
+        let divisor = pow10(self.scale - scale);
+        Decimal::new(divide_rounded(&self.digits, &divisor, rounding), scale)
+    }
+
+    fn trim_zeros(mut self, min_scale: u32) -> Decimal {
+        let ten = BigInt::from(10);
+
+        while self.scale > min_scale && (&self.digits % &ten).sign() == Sign::NoSign {
+            self.digits /= &ten;
+            self.scale -= 1;
+        }
+
+        self
+    }
+
+    fn rescale(&self, scale: u32) -> BigInt {
+        &self.digits * pow10(scale - self.scale)
+    }
+}
+
+fn pow10(exponent: u32) -> BigInt {
+    BigInt::from(10).pow(exponent)
+}
+
+// Divides and rounds the quotient by the remainder that was cut off.
+fn divide_rounded(numerator: &BigInt, denominator: &BigInt, rounding: Rounding) -> BigInt {
+    let quotient = numerator / denominator;
+    let remainder = numerator % denominator;
+
+    if remainder.sign() == Sign::NoSign {
+        return quotient;
+    }
+
+    let negative = (numerator.sign() == Sign::Minus) != (denominator.sign() == Sign::Minus);
+    let half = (remainder.magnitude() * 2u32).cmp(denominator.magnitude());
+
+    let away_from_zero = match rounding {
+        Rounding::Up => true,
+        Rounding::Down => false,
+        Rounding::Ceiling => !negative,
+        Rounding::Floor => negative,
+        Rounding::HalfUp => half != Ordering::Less,
+        Rounding::HalfDown => half == Ordering::Greater,
+        Rounding::HalfEven => {
+            half == Ordering::Greater || (half == Ordering::Equal && quotient.bit(0))
+        }
+    };
+
+    match (away_from_zero, negative) {
+        (false, _) => quotient,
+        (true, false) => quotient + 1,
+        (true, true) => quotient - 1,
+    }
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Decimal {}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Decimals are compared by their value, so 1.5 and 1.50 are equal.
+impl Ord for Decimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let scale = self.scale.max(other.scale);
+        self.rescale(scale).cmp(&other.rescale(scale))
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sign = if self.digits.sign() == Sign::Minus {
+            "-"
+        } else {
+            ""
+        };
+
+        let scale = self.scale as usize;
+        let digits = format!(
+            "{:0>width$}",
+            self.digits.magnitude().to_string(),
+            width = scale + 1
+        );
+        let (integer, fraction) = digits.split_at(digits.len() - scale);
+
+        if fraction.is_empty() {
+            write!(f, "{}{}", sign, integer)
+        } else {
+            write!(f, "{}{}.{}", sign, integer, fraction)
+        }
+    }
+}
@@ -1,11 +1,16 @@
-use crate::object::Object;
-use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use crate::object::{EvalError, Object};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
 
 pub type Env = Rc<RefCell<Environment>>;
 
 #[derive(Debug, Clone, Default)]
 pub struct Environment {
     store: HashMap<String, Rc<Object>>,
+    constants: HashSet<String>,
     outer: Option<Env>,
 }
 
@@ -13,6 +18,7 @@ impl Environment {
     pub fn new_enclosed_environment(outer: &Env) -> Self {
         Environment {
             store: Default::default(),
+            constants: Default::default(),
             outer: Some(outer.clone()),
         }
     }
@@ -20,18 +26,61 @@ impl Environment {
     pub fn get(&self, name: &str) -> Option<Rc<Object>> {
         match self.store.get(name) {
             Some(obj) => Some(Rc::clone(obj)),
-            None => { 
+            None => {
                 // trying to get the object of outer environment
                 if let Some(outer) = &self.outer {
                     return outer.borrow().get(name);
-                } 
+                }
 
-                return None;
+                None
             }
         }
     }
 
-    pub fn set(&mut self, name: String, val: Rc<Object>) {
+    // Declares (or redeclares) a binding in the current scope.
+    // Constants of the current scope can't be redeclared.
+    pub fn set(&mut self, name: String, val: Rc<Object>) -> Result<(), EvalError> {
+        if self.constants.contains(&name) {
+            return Err(format!("redeclaring constant {} is not allowed", name));
+        }
+
+        self.store.insert(name, val);
+        Ok(())
+    }
+
+    // Declares a constant binding in the current scope,
+    // the name must not be declared in this scope already.
+    pub fn set_const(&mut self, name: String, val: Rc<Object>) -> Result<(), EvalError> {
+        if self.constants.contains(&name) {
+            return Err(format!("redeclaring constant {} is not allowed", name));
+        }
+
+        if self.store.contains_key(&name) {
+            return Err(format!(
+                "{} is already declared and can not be redeclared as a constant",
+                name
+            ));
+        }
+
+        self.constants.insert(name.clone());
         self.store.insert(name, val);
+        Ok(())
+    }
+
+    // Updates an existing binding, looking it up through the outer environments.
+    pub fn assign(&mut self, name: &str, val: Rc<Object>) -> Result<(), EvalError> {
+        if self.store.contains_key(name) {
+            if self.constants.contains(name) {
+                return Err(format!("assigning to constant {} is not allowed", name));
+            }
+
+            self.store.insert(name.to_string(), val);
+            return Ok(());
+        }
+
+        match &self.outer {
+            Some(outer) => outer.borrow_mut().assign(name, val),
+            None => Err(format!("variable {} is not declared", name)),
+        }
     }
 }
@@ -0,0 +1,68 @@
+use crate::{env::Env, object::Object};
+use ast::{
+    expression::Expression,
+    statement::{BlockStatement, For, Statement},
+};
+use std::{cell::RefCell, rc::Rc, vec};
+
+// A generator keeps the position of a suspended function body as an explicit
+// stack of frames, so the evaluator can resume it where the last yield left off.
+#[derive(Debug, Clone)]
+pub struct Generator {
+    pub frames: Vec<GeneratorFrame>,
+    pub running: bool,
+    // the actions deferred by the body so far, they run once the generator finishes
+    pub deferred: Vec<Deferred>,
+}
+
+// An expression registered by a defer statement, evaluated in the scope it was
+// registered in when the enclosing function returns.
+#[derive(Debug, Clone)]
+pub struct Deferred {
+    pub action: Expression,
+    pub env: Env,
+}
+
+#[derive(Debug, Clone)]
+pub enum GeneratorFrame {
+    // The statements of a block, executed one by one.
+    Block {
+        statements: Vec<Statement>,
+        index: usize,
+        env: Env,
+    },
+    // A C-style for loop, the increment runs before every iteration except the first one.
+    For {
+        stmt: Box<For>,
+        started: bool,
+        env: Env,
+    },
+    // A for-in loop that binds every item of the iterator to the variable.
+    ForIn {
+        variable: String,
+        iterator: ObjectIterator,
+        body: BlockStatement,
+        label: Option<String>,
+        env: Env,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub enum ObjectIterator {
+    Elements(vec::IntoIter<Rc<Object>>),
+    Generator(Rc<RefCell<Generator>>),
+}
+
+impl Generator {
+    pub fn new(statements: Vec<Statement>, env: Env) -> Self {
+        Self {
+            frames: vec![GeneratorFrame::Block {
+                statements,
+                index: 0,
+                env,
+            }],
+            running: false,
+            deferred: vec![],
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use crate::object::{EvalError, Object};
+use core::fmt;
+use num_bigint::BigInt;
+
+// Only the immutable values can be used as the keys of a hash.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HashKey {
+    Boolean(bool),
+    Integer(i64),
+    BigInteger(BigInt),
+    String(String),
+    Bytes(Vec<u8>),
+}
+
+impl HashKey {
+    pub fn from_object(obj: &Object) -> Result<Self, EvalError> {
+        match obj {
+            Object::Boolean(v) => Ok(HashKey::Boolean(*v)),
+            Object::Integer(v) => Ok(HashKey::Integer(*v)),
+            Object::BigInteger(v) => Ok(HashKey::BigInteger(v.clone())),
+            Object::String(v) => Ok(HashKey::String(v.clone())),
+            Object::Bytes(v) => Ok(HashKey::Bytes(v.clone())),
+            _ => Err(format!("type {} can not be a hash key", obj.type_name())),
+        }
+    }
+
+    // The elements of a set are stored as its keys.
+    pub fn from_set_element(obj: &Object) -> Result<Self, EvalError> {
+        HashKey::from_object(obj)
+            .map_err(|_| format!("type {} can not be an element of a set", obj.type_name()))
+    }
+
+    pub fn to_object(&self) -> Object {
+        match self {
+            HashKey::Boolean(v) => Object::Boolean(*v),
+            HashKey::Integer(v) => Object::Integer(*v),
+            HashKey::BigInteger(v) => Object::BigInteger(v.clone()),
+            HashKey::String(v) => Object::String(v.clone()),
+            HashKey::Bytes(v) => Object::Bytes(v.clone()),
+        }
+    }
+}
+
+impl fmt::Display for HashKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HashKey::Boolean(v) => write!(f, "{}", v),
+            HashKey::Integer(v) => write!(f, "{}", v),
+            HashKey::BigInteger(v) => write!(f, "{}", v),
+            HashKey::String(v) => write!(f, "{}", v),
+            HashKey::Bytes(v) => write!(f, "b\"{}\"", v.escape_ascii()),
+        }
+    }
+}
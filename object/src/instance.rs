@@ -0,0 +1,106 @@
+use crate::object::{EvalError, Object};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::Rc,
+};
+
+pub type DisplayHook = fn(&Rc<Instance>) -> Option<Result<String, EvalError>>;
+
+thread_local! {
+    static DISPLAY_HOOK: Cell<Option<DisplayHook>> = const { Cell::new(None) };
+}
+
+// Converting an instance to text may run its to_string method, which only the
+// evaluator can do, so the evaluator registers itself here.
+pub fn set_display_hook(hook: DisplayHook) {
+    DISPLAY_HOOK.with(|display_hook| display_hook.set(Some(hook)));
+}
+
+pub fn display_hook() -> Option<DisplayHook> {
+    DISPLAY_HOOK.with(Cell::get)
+}
+
+#[derive(Debug)]
+pub struct StructType {
+    pub name: String,
+    pub fields: Vec<String>,
+    // methods are added by the impl blocks after the struct is declared
+    pub methods: RefCell<HashMap<String, Rc<Object>>>,
+    pub traits: RefCell<Vec<Rc<TraitType>>>,
+}
+
+impl StructType {
+    pub fn new(name: String, fields: Vec<String>) -> Self {
+        Self {
+            name,
+            fields,
+            methods: RefCell::new(HashMap::new()),
+            traits: RefCell::new(vec![]),
+        }
+    }
+
+    pub fn method(&self, name: &str) -> Option<Rc<Object>> {
+        self.methods.borrow().get(name).cloned()
+    }
+
+    pub fn implements(&self, trait_type: &Rc<TraitType>) -> bool {
+        self.traits
+            .borrow()
+            .iter()
+            .any(|implemented| Rc::ptr_eq(implemented, trait_type))
+    }
+}
+
+#[derive(Debug)]
+pub struct TraitType {
+    pub name: String,
+    // the names of the required methods with their number of parameters
+    pub required: Vec<(String, usize)>,
+    // the default methods, copied into the structs that don't define them
+    pub methods: HashMap<String, Rc<Object>>,
+}
+
+impl TraitType {
+    // The number of parameters of a method of the trait, None when the trait
+    // has no method with that name.
+    pub fn arity(&self, name: &str) -> Option<usize> {
+        self.required
+            .iter()
+            .find(|(method, _)| method == name)
+            .map(|(_, arity)| *arity)
+            .or_else(|| match self.methods.get(name).map(|method| &**method) {
+                Some(Object::Function(params, _, _))
+                | Some(Object::GeneratorFunction(params, _, _)) => Some(params.len()),
+                _ => None,
+            })
+    }
+}
+
+#[derive(Debug)]
+pub struct Instance {
+    pub ty: Rc<StructType>,
+    // the values are in the same order as the fields of the struct
+    pub values: RefCell<Vec<Rc<Object>>>,
+}
+
+impl Instance {
+    pub fn field(&self, name: &str) -> Option<Rc<Object>> {
+        self.ty
+            .fields
+            .iter()
+            .position(|field| field == name)
+            .map(|index| Rc::clone(&self.values.borrow()[index]))
+    }
+
+    // Replaces the value of the field, returns false when the struct has no such field.
+    pub fn set_field(&self, name: &str, value: Rc<Object>) -> bool {
+        match self.ty.fields.iter().position(|field| field == name) {
+            Some(index) => {
+                self.values.borrow_mut()[index] = value;
+                true
+            }
+            None => false,
+        }
+    }
+}
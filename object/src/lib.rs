@@ -1,2 +1,6 @@
+pub mod decimal;
 pub mod env;
+pub mod generator;
+pub mod hash;
+pub mod instance;
 pub mod object;
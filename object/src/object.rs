@@ -1,34 +1,239 @@
-use crate::env::Env;
-use ast::{expression::Identifier, statement::BlockStatement};
+use crate::{
+    decimal::Decimal,
+    env::Env,
+    generator::Generator,
+    hash::HashKey,
+    instance::{display_hook, Instance, StructType, TraitType},
+};
+use ast::{expression::Identifier, statement::BlockStatement, Node};
 use core::fmt;
-use std::rc::Rc;
+use num_bigint::BigInt;
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet},
+    rc::Rc,
+};
 
 pub type EvalError = String;
 pub type BuiltinFunc = fn(Vec<Rc<Object>>) -> Rc<Object>;
+pub type ScopedBuiltinFunc = fn(Vec<Rc<Object>>, &Env) -> Result<Rc<Object>, EvalError>;
 
 #[derive(Debug, Clone)]
 pub enum Object {
     Integer(i64),
+    BigInteger(BigInt),
+    Decimal(Decimal),
     Boolean(bool),
     String(String),
+    Bytes(Vec<u8>),
+    // arrays and hashes are shared by reference, so a change made through
+    // one variable is seen through every other one holding the collection
+    Array(RefCell<Vec<Rc<Object>>>),
+    Tuple(Vec<Rc<Object>>),
+    Hash(RefCell<BTreeMap<HashKey, Rc<Object>>>),
+    // the elements of a set are kept as keys, so only the values that can be a
+    // hash key can be in a set
+    Set(RefCell<BTreeSet<HashKey>>),
     ReturnValue(Rc<Object>),
+    // the signals of break and continue, with the label of the loop they target
+    Break(Option<String>),
+    Continue(Option<String>),
+    TailCall(Rc<Object>, Vec<Rc<Object>>),
     Function(Vec<Identifier>, BlockStatement, Env),
+    GeneratorFunction(Vec<Identifier>, BlockStatement, Env),
+    Generator(Rc<RefCell<Generator>>),
     Builtin(BuiltinFunc),
+    // the builtins that run code, they get the scope of their caller
+    ScopedBuiltin(ScopedBuiltinFunc),
+    Struct(Rc<StructType>),
+    Instance(Rc<Instance>),
+    Trait(Rc<TraitType>),
+    // the code passed to quote, with the unquoted values spliced in
+    Quote(Node),
+    Macro(Vec<Identifier>, BlockStatement, Env),
     Error(String),
     Null,
 }
 
+thread_local! {
+    // the collections whose rendering is in progress, from the outermost one
+    static RENDERING: RefCell<Vec<*const Object>> = const { RefCell::new(Vec::new()) };
+}
+
+// A collection can contain itself through its shared reference, so where it
+// shows up again inside its own rendering it is written as the placeholder.
+fn render_once(
+    object: &Object,
+    placeholder: String,
+    render: impl FnOnce() -> Result<String, EvalError>,
+) -> Result<String, EvalError> {
+    let pointer = object as *const Object;
+
+    if RENDERING.with_borrow(|rendering| rendering.contains(&pointer)) {
+        return Ok(placeholder);
+    }
+
+    RENDERING.with_borrow_mut(|rendering| rendering.push(pointer));
+    let result = render();
+    RENDERING.with_borrow_mut(|rendering| rendering.pop());
+
+    result
+}
+
+fn render_all(elements: &[Rc<Object>], run_to_string: bool) -> Result<String, EvalError> {
+    Ok(elements
+        .iter()
+        .map(|element| element.render(run_to_string))
+        .collect::<Result<Vec<String>, EvalError>>()?
+        .join(", "))
+}
+
 impl fmt::Display for Object {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Object::Integer(v) => write!(f, "{}", v),
+            Object::BigInteger(v) => write!(f, "{}", v),
+            Object::Decimal(v) => write!(f, "{}", v),
             Object::Boolean(v) => write!(f, "{}", v),
             Object::String(v) => write!(f, "{}", v),
+            Object::Bytes(v) => write!(f, "b\"{}\"", v.escape_ascii()),
+            // the plain display runs no to_string method, so it can't fail
+            Object::Array(_) | Object::Tuple(_) | Object::Hash(_) | Object::Instance(_) => {
+                write!(f, "{}", self.render(false).unwrap_or_default())
+            }
+            Object::Set(elements) => write!(
+                f,
+                "#{{{}}}",
+                elements
+                    .borrow()
+                    .iter()
+                    .map(|element| element.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
             Object::Error(v) => write!(f, "{}", v),
             Object::ReturnValue(expr) => write!(f, "{}", expr),
-            Object::Builtin(_) => write!(f, "[builtin func]"),
+            Object::Break(_) => write!(f, "[break]"),
+            Object::Continue(_) => write!(f, "[continue]"),
+            Object::TailCall(_, _) => write!(f, "[tail call]"),
+            Object::Builtin(_) | Object::ScopedBuiltin(_) => write!(f, "[builtin func]"),
             Object::Function(_, _, _) => write!(f, "[func]"),
+            Object::GeneratorFunction(_, _, _) => write!(f, "[generator func]"),
+            Object::Generator(_) => write!(f, "[generator]"),
+            Object::Struct(ty) => write!(f, "[struct {}]", ty.name),
+            Object::Trait(trait_type) => write!(f, "[trait {}]", trait_type.name),
+            Object::Quote(node) => write!(f, "quote({})", node),
+            Object::Macro(_, _, _) => write!(f, "[macro]"),
             Object::Null => write!(f, "null"),
         }
     }
 }
+
+impl Object {
+    // Converts the value to text the way print does, the instances with a
+    // to_string method are written by it and its error is the error of the
+    // conversion.
+    pub fn to_text(&self) -> Result<String, EvalError> {
+        self.render(true)
+    }
+
+    fn render(&self, run_to_string: bool) -> Result<String, EvalError> {
+        match self {
+            Object::Array(elements) => render_once(self, "[...]".to_string(), || {
+                Ok(format!(
+                    "[{}]",
+                    render_all(&elements.borrow(), run_to_string)?
+                ))
+            }),
+            Object::Tuple(elements) if elements.len() == 1 => {
+                Ok(format!("({},)", elements[0].render(run_to_string)?))
+            }
+            Object::Tuple(elements) => Ok(format!("({})", render_all(elements, run_to_string)?)),
+            Object::Hash(pairs) => render_once(self, "{...}".to_string(), || {
+                let pairs = pairs
+                    .borrow()
+                    .iter()
+                    .map(|(key, value)| Ok(format!("{}: {}", key, value.render(run_to_string)?)))
+                    .collect::<Result<Vec<String>, EvalError>>()?;
+
+                Ok(format!("{{{}}}", pairs.join(", ")))
+            }),
+            Object::Instance(instance) => {
+                if let Some(text) = display_hook()
+                    .filter(|_| run_to_string)
+                    .and_then(|hook| hook(instance))
+                {
+                    return text;
+                }
+
+                render_once(self, format!("{} {{...}}", instance.ty.name), || {
+                    let fields = instance
+                        .ty
+                        .fields
+                        .iter()
+                        .zip(instance.values.borrow().iter())
+                        .map(|(field, value)| {
+                            Ok(format!("{}: {}", field, value.render(run_to_string)?))
+                        })
+                        .collect::<Result<Vec<String>, EvalError>>()?;
+
+                    Ok(format!("{} {{ {} }}", instance.ty.name, fields.join(", ")))
+                })
+            }
+            _ => Ok(self.to_string()),
+        }
+    }
+
+    pub fn array(elements: Vec<Rc<Object>>) -> Self {
+        Object::Array(RefCell::new(elements))
+    }
+
+    pub fn hash(pairs: BTreeMap<HashKey, Rc<Object>>) -> Self {
+        Object::Hash(RefCell::new(pairs))
+    }
+
+    pub fn set(elements: BTreeSet<HashKey>) -> Self {
+        Object::Set(RefCell::new(elements))
+    }
+
+    // A return, break or continue skips the rest of the enclosing blocks until
+    // it reaches the function or the loop it belongs to.
+    pub fn is_jump(&self) -> bool {
+        matches!(
+            self,
+            Object::ReturnValue(_) | Object::Break(_) | Object::Continue(_)
+        )
+    }
+
+    pub fn type_name(&self) -> String {
+        let name = match self {
+            Object::Integer(_) | Object::BigInteger(_) => "integer",
+            Object::Decimal(_) => "decimal",
+            Object::Boolean(_) => "boolean",
+            Object::String(_) => "string",
+            Object::Bytes(_) => "bytes",
+            Object::Array(_) => "array",
+            Object::Tuple(_) => "tuple",
+            Object::Hash(_) => "hash",
+            Object::Set(_) => "set",
+            Object::ReturnValue(value) => return value.type_name(),
+            Object::Break(_) => "break",
+            Object::Continue(_) => "continue",
+            Object::TailCall(_, _) => "tail call",
+            Object::Function(_, _, _)
+            | Object::GeneratorFunction(_, _, _)
+            | Object::Builtin(_)
+            | Object::ScopedBuiltin(_) => "function",
+            Object::Generator(_) => "generator",
+            Object::Error(_) => "error",
+            Object::Struct(_) => "struct",
+            Object::Trait(_) => "trait",
+            Object::Quote(_) => "quote",
+            Object::Macro(_, _, _) => "macro",
+            Object::Instance(instance) => return instance.ty.name.clone(),
+            Object::Null => "null",
+        };
+
+        name.to_string()
+    }
+}
@@ -1,10 +1,15 @@
 use ast::{
     expression::{
-        BinaryExpression, Boolean, Expression, FunctionCall, Identifier, Integer, Literal,
-        StringType, UnaryExpression, UnaryOperator, UnaryOperatorType,
+        Array, Assignment, BigInteger, BinaryExpression, Boolean, Bytes, Comprehension,
+        Conditional, Decimal, Expression, Field, FunctionCall, Hash, HashComprehension, HashEntry,
+        Identifier, Index, Integer, ListComprehension, Literal, MethodCall, Set, Slice, Spread,
+        StringType, Tuple, UnaryExpression, UnaryOperator, UnaryOperatorType,
     },
     program::Program,
-    statement::{BlockStatement, For, Function, If, Return, Statement, Variable},
+    statement::{
+        BlockStatement, Break, Continue, Defer, For, ForIn, Function, If, Impl, Return, Signature,
+        Statement, Struct, Trait, TupleDeclaration, Variable, Yield,
+    },
     Node,
 };
 use lexer::Lexer;
@@ -21,24 +26,75 @@ pub struct Parser<'a> {
     lexer: &'a mut Lexer,
     current_token: Token,
     peek_token: Token,
+    // the doc comments written right before the current and the peek token
+    current_docs: Vec<String>,
+    peek_docs: Vec<String>,
     errors: Vec<ParseError>,
+    // set by the first lexer error, every token after it reads as the end of input
+    lexer_failed: bool,
+    // the labels of the loops around the statement being parsed, innermost
+    // last, with None for a loop without a label
+    loops: Vec<Option<String>>,
+}
+
+// Doc comments are not passed to the parser as tokens, they are collected
+// for the token that follows them instead.
+// A lexer error is kept with the parse errors and ends the input, the lexer
+// is not read after it so the rest of the input can't add errors of its own.
+fn read_token(
+    lexer: &mut Lexer,
+    docs: &mut Vec<String>,
+    errors: &mut Vec<ParseError>,
+    lexer_failed: &mut bool,
+) -> Token {
+    if *lexer_failed {
+        return end_of_input();
+    }
+
+    loop {
+        let token = match lexer.next_token() {
+            Ok(token) => token,
+            Err(error) => {
+                errors.push(error);
+                *lexer_failed = true;
+
+                return end_of_input();
+            }
+        };
+
+        match token.kind {
+            TokenKind::DocComment(doc) => docs.push(doc),
+            _ => return token,
+        }
+    }
+}
+
+fn end_of_input() -> Token {
+    Token {
+        kind: TokenKind::EOF,
+        span: Span::new_empty_span(),
+    }
 }
 
 impl<'a> Parser<'a> {
     // Init Parser
     pub fn new(lexer: &'a mut Lexer) -> Self {
-        let current_token = lexer
-            .next_token()
-            .expect("An error raised when reading current_token by lexer at parser");
-        let peek_token = lexer
-            .next_token()
-            .expect("An error raised when reading peek_token by lexer at parser");
+        let mut errors = vec![];
+        let mut lexer_failed = false;
+        let mut current_docs = vec![];
+        let current_token = read_token(lexer, &mut current_docs, &mut errors, &mut lexer_failed);
+        let mut peek_docs = vec![];
+        let peek_token = read_token(lexer, &mut peek_docs, &mut errors, &mut lexer_failed);
 
         Parser {
             lexer,
             current_token,
             peek_token,
-            errors: vec![],
+            current_docs,
+            peek_docs,
+            errors,
+            lexer_failed,
+            loops: vec![],
         }
     }
 
@@ -56,8 +112,23 @@ impl<'a> Parser<'a> {
             TokenKind::If => self.parse_if_statement(),
             TokenKind::Function => self.parse_function_statement(),
             TokenKind::Return => self.parse_return_statement(),
+            TokenKind::Yield => self.parse_yield_statement(),
+            TokenKind::Hashtag if self.peek_token_is(TokenKind::LeftBrace) => {
+                self.parse_expression_statement()
+            }
             TokenKind::Hashtag => self.parse_variable_declaration(),
-            TokenKind::For => self.parse_for_statement(),
+            TokenKind::Const => self.parse_constant_declaration(),
+            TokenKind::For => self.parse_for_statement(None),
+            TokenKind::Identifier { .. } if self.peek_token_is(TokenKind::Colon) => {
+                self.parse_labeled_loop()
+            }
+            TokenKind::Break | TokenKind::Continue => self.parse_jump_statement(),
+            TokenKind::LeftBrace => self.parse_bare_block_statement(),
+            TokenKind::Struct => self.parse_struct_statement(),
+            TokenKind::Impl => self.parse_impl_statement(),
+            TokenKind::Defer => self.parse_defer_statement(),
+            TokenKind::Trait => self.parse_trait_statement(),
+            TokenKind::Macro => self.parse_macro_statement(),
             _ => self.parse_expression_statement(),
         }
     }
@@ -69,6 +140,8 @@ impl<'a> Parser<'a> {
         while self.current_token.kind != TokenKind::EOF {
             match self.parse_statement() {
                 Ok(statement) => program.body.push(statement),
+                // the statement was cut short by the lexer error, which is already reported
+                Err(_) if self.lexer_failed => {}
                 Err(error) => self.errors.push(error),
             }
 
@@ -84,13 +157,25 @@ impl<'a> Parser<'a> {
 
     fn next_token(&mut self) -> Token {
         self.current_token = self.peek_token.clone();
-        self.peek_token = self
-            .lexer
-            .next_token()
-            .expect("Failed to read next_token in parser");
+        self.current_docs = std::mem::take(&mut self.peek_docs);
+        self.peek_token = read_token(
+            self.lexer,
+            &mut self.peek_docs,
+            &mut self.errors,
+            &mut self.lexer_failed,
+        );
         self.peek_token.clone()
     }
 
+    // Takes the doc comments of the current token, joined into a single text.
+    fn take_doc(&mut self) -> Option<String> {
+        if self.current_docs.is_empty() {
+            return None;
+        }
+
+        Some(std::mem::take(&mut self.current_docs).join("\n"))
+    }
+
     fn current_token_is(&self, token_kind: TokenKind) -> bool {
         self.current_token.kind == token_kind
     }
@@ -169,10 +254,105 @@ impl<'a> Parser<'a> {
     }
 
     // Parse statements
-    fn parse_for_statement(&mut self) -> Result<Statement, ParseError> {
+    fn parse_labeled_loop(&mut self) -> Result<Statement, ParseError> {
+        let label = self.parse_label()?;
+
+        self.next_token(); // consume the label
+        self.next_token(); // consume the colon
+
+        if !self.current_token_is(TokenKind::For) {
+            return Err(format!(
+                "expected a loop after the label {} but got: {}",
+                label.name, self.current_token.kind
+            ));
+        }
+
+        self.parse_for_statement(Some(label))
+    }
+
+    fn parse_label(&mut self) -> Result<Identifier, ParseError> {
+        match self.current_token.kind.clone() {
+            TokenKind::Identifier { name } => Ok(Identifier {
+                name,
+                span: self.current_token.span.clone(),
+            }),
+            _ => Err(format!(
+                "expected an identifier as the label of the loop but got: {}",
+                self.current_token.kind
+            )),
+        }
+    }
+
+    // The label of a break or continue must belong to one of the loops around it,
+    // the loops outside of the enclosing function don't count.
+    fn parse_jump_statement(&mut self) -> Result<Statement, ParseError> {
         let start = self.current_token.span.start;
+        let keyword = self.current_token.kind.clone();
+
+        let label = if matches!(self.peek_token.kind, TokenKind::Identifier { .. }) {
+            self.next_token(); // consume the keyword
+            Some(self.parse_label()?)
+        } else {
+            None
+        };
+
+        match &label {
+            None if self.loops.is_empty() => {
+                return Err(format!("{} can only be used inside of a loop", keyword));
+            }
+            Some(label)
+                if !self
+                    .loops
+                    .iter()
+                    .any(|loop_label| loop_label.as_deref() == Some(label.name.as_str())) =>
+            {
+                return Err(format!("unknown loop label {}", label.name));
+            }
+            _ => {}
+        }
+
+        if self.peek_token_is(TokenKind::Semicolon) {
+            self.next_token();
+        }
+
+        let span = Span {
+            start,
+            end: self.current_token.span.end,
+        };
+
+        Ok(match keyword {
+            TokenKind::Break => Statement::Break(Break { label, span }),
+            _ => Statement::Continue(Continue { label, span }),
+        })
+    }
+
+    // Parses the body of a loop with its label in scope of the break and continue statements.
+    fn parse_loop_body<T>(
+        &mut self,
+        label: &Option<Identifier>,
+        parse: impl FnOnce(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<T, ParseError> {
+        self.loops
+            .push(label.as_ref().map(|label| label.name.clone()));
+        let body = parse(self);
+        self.loops.pop();
+
+        body
+    }
+
+    fn parse_for_statement(&mut self, label: Option<Identifier>) -> Result<Statement, ParseError> {
+        let start = match &label {
+            Some(label) => label.span.start,
+            None => self.current_token.span.start,
+        };
         self.next_token(); // consume for token
 
+        if matches!(self.current_token.kind, TokenKind::Identifier { .. })
+            && self.peek_token_is(TokenKind::In)
+        {
+            return self.parse_for_in_statement(start, label);
+        }
+
         let mut initializer: Option<Variable> = None;
         if let Statement::VariableDeclaration(var) = self.parse_variable_declaration()? {
             initializer = Some(var);
@@ -180,35 +360,18 @@ impl<'a> Parser<'a> {
 
         self.expect_current(TokenKind::Semicolon)?;
 
-        let condition: Option<Expression>;
-        match self.parse_expression(Precedence::Lowest) {
-            Ok(result) => {
-                condition = Some(result.0);
-            }
-            Err(e) => {
-                dbg!(e.clone());
-                return Err(e);
-            }
-        }
+        let condition: Option<Expression> = Some(self.parse_expression(Precedence::Lowest)?.0);
 
         self.expect_peek(TokenKind::Semicolon)?;
         self.next_token();
 
-        let increment: Option<Expression>;
-        match self.parse_expression(Precedence::Lowest) {
-            Ok(result) => {
-                increment = Some(result.0);
-            }
-            Err(e) => {
-                return Err(e);
-            }
-        }
+        let increment: Option<Expression> = Some(self.parse_expression(Precedence::Lowest)?.0);
 
         self.next_token(); // consume increment token
 
         let body: Box<BlockStatement>;
         if self.current_token_is(TokenKind::LeftBrace) {
-            body = Box::new(self.parse_block_statement()?);
+            body = Box::new(self.parse_loop_body(&label, Self::parse_block_statement)?);
 
             if !self.current_token_is(TokenKind::RightBrace) {
                 return Err("expected to close the statement with a right brace".to_string());
@@ -218,9 +381,9 @@ impl<'a> Parser<'a> {
                 self.next_token();
             }
         } else {
-            return Err(format!(
-                "expected to get a block statement declaration with right brace",
-            ));
+            return Err(
+                "expected to get a block statement declaration with right brace".to_string(),
+            );
         }
 
         Ok(Statement::For(For {
@@ -228,6 +391,50 @@ impl<'a> Parser<'a> {
             condition,
             increment,
             body,
+            label,
+            span: Span {
+                start,
+                end: self.current_token.span.end,
+            },
+        }))
+    }
+
+    fn parse_for_in_statement(
+        &mut self,
+        start: usize,
+        label: Option<Identifier>,
+    ) -> Result<Statement, ParseError> {
+        let variable = match self.current_token.kind.clone() {
+            TokenKind::Identifier { name } => Identifier {
+                name,
+                span: self.current_token.span.clone(),
+            },
+            _ => {
+                return Err(format!(
+                    "expected an identifier as the variable of the loop but got: {}",
+                    self.current_token.kind
+                ))
+            }
+        };
+
+        self.next_token(); // consume the variable
+        self.next_token(); // consume in token
+
+        let (iterable, _) = self.parse_expression(Precedence::Lowest)?;
+
+        self.expect_peek(TokenKind::LeftBrace)?;
+
+        let body = Box::new(self.parse_loop_body(&label, Self::parse_closed_block)?);
+
+        if self.peek_token_is(TokenKind::Semicolon) {
+            self.next_token();
+        }
+
+        Ok(Statement::ForIn(ForIn {
+            variable,
+            iterable,
+            body,
+            label,
             span: Span {
                 start,
                 end: self.current_token.span.end,
@@ -236,8 +443,86 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_variable_declaration(&mut self) -> Result<Statement, ParseError> {
+        if self.peek_token_is(TokenKind::LeftParen) {
+            return self.parse_tuple_declaration(false);
+        }
+
+        Ok(Statement::VariableDeclaration(self.parse_variable()?))
+    }
+
+    fn parse_constant_declaration(&mut self) -> Result<Statement, ParseError> {
+        if self.peek_token_is(TokenKind::LeftParen) {
+            return self.parse_tuple_declaration(true);
+        }
+
+        Ok(Statement::ConstantDeclaration(self.parse_variable()?))
+    }
+
+    fn parse_tuple_declaration(&mut self, constant: bool) -> Result<Statement, ParseError> {
+        let start = self.current_token.span.start;
+        self.next_token(); // consume sharp or const token
+        self.next_token(); // consume the left paren
+
+        let mut names: Vec<Identifier> = Vec::new();
+
+        while !self.current_token_is(TokenKind::RightParen) {
+            match self.current_token.kind.clone() {
+                TokenKind::Identifier { name } => {
+                    names.push(Identifier {
+                        name,
+                        span: self.current_token.span.clone(),
+                    });
+                }
+                _ => {
+                    return Err(format!(
+                        "expected an identifier as the name of the variable but got: {}",
+                        self.current_token.kind
+                    ))
+                }
+            }
+
+            self.next_token(); // consume the name
+
+            if self.current_token_is(TokenKind::Comma) {
+                self.next_token();
+            } else if !self.current_token_is(TokenKind::RightParen) {
+                return Err(format!(
+                    "expected comma or right paren after the name but got: {}",
+                    self.current_token.kind
+                ));
+            }
+        }
+
+        self.next_token(); // consume the right paren
+        self.expect_current(TokenKind::Assign)?;
+
+        let (expr, span) = self.parse_expression(Precedence::Lowest)?;
+
+        if self.peek_token_is(TokenKind::Semicolon) {
+            self.next_token();
+        }
+
+        Ok(Statement::TupleDeclaration(TupleDeclaration {
+            names,
+            expr,
+            constant,
+            span: Span {
+                start,
+                end: span.end,
+            },
+        }))
+    }
+
+    fn parse_variable(&mut self) -> Result<Variable, ParseError> {
         let start = self.current_token.span.start;
-        self.next_token(); // consume sharp token
+        self.next_token(); // consume sharp or const token
+
+        if !matches!(self.current_token.kind, TokenKind::Identifier { .. }) {
+            return Err(format!(
+                "expected an identifier as the name of the variable but got: {}",
+                self.current_token.kind
+            ));
+        }
 
         let identifier = self.current_token.clone(); // export the name of the identifier
         self.next_token(); // consume thte identofier
@@ -250,14 +535,14 @@ impl<'a> Parser<'a> {
             self.next_token();
         }
 
-        Ok(Statement::VariableDeclaration(Variable {
+        Ok(Variable {
             identifier,
             expr,
             span: Span {
                 start,
                 end: span.end,
             },
-        }))
+        })
     }
 
     fn parse_expression_series(
@@ -282,8 +567,18 @@ impl<'a> Parser<'a> {
 
         self.next_token(); // consume the starting token
 
-        series.push(self.parse_expression(Precedence::Lowest)?.0); // parse the first expression
+        series.push(self.parse_series_element()?); // parse the first expression
+
+        self.parse_expression_series_rest(start, series, end)
+    }
 
+    // Parses the rest of a series whose first expression is already parsed.
+    fn parse_expression_series_rest(
+        &mut self,
+        start: usize,
+        mut series: Vec<Expression>,
+        end: TokenKind,
+    ) -> Result<(Vec<Expression>, Span), ParseError> {
         // !self.peek_token_is(end.clone())
         while self.peek_token_is(TokenKind::Comma) {
             self.next_token(); // consume the current expression
@@ -295,7 +590,7 @@ impl<'a> Parser<'a> {
 
             self.next_token(); // consume the comma
 
-            series.push(self.parse_expression(Precedence::Lowest)?.0);
+            series.push(self.parse_series_element()?);
         }
 
         if self.peek_token_is(end.clone()) {
@@ -318,8 +613,48 @@ impl<'a> Parser<'a> {
         ))
     }
 
+    // An element of a series is an expression, or a spread of the elements of
+    // an iterable when it starts with an ellipsis.
+    fn parse_series_element(&mut self) -> Result<Expression, ParseError> {
+        if self.current_token_is(TokenKind::Ellipsis) {
+            return Ok(Expression::Spread(self.parse_spread()?));
+        }
+
+        Ok(self.parse_expression(Precedence::Lowest)?.0)
+    }
+
+    fn parse_spread(&mut self) -> Result<Spread, ParseError> {
+        let start = self.current_token.span.start;
+        self.next_token(); // consume the ellipsis
+
+        let (argument, span) = self.parse_expression(Precedence::Lowest)?;
+
+        Ok(Spread {
+            argument: Box::new(argument),
+            span: Span {
+                start,
+                end: span.end,
+            },
+        })
+    }
+
     fn parse_function_statement(&mut self) -> Result<Statement, ParseError> {
+        let signature = self.parse_function_signature()?;
+
+        Ok(Statement::Function(self.parse_function_body(signature)?))
+    }
+
+    fn parse_macro_statement(&mut self) -> Result<Statement, ParseError> {
+        let signature = self.parse_function_signature()?;
+
+        Ok(Statement::Macro(self.parse_function_body(signature)?))
+    }
+
+    // Parses a function up to its body, the current token is left on the token
+    // that follows the parameters.
+    fn parse_function_signature(&mut self) -> Result<Signature, ParseError> {
         let start = self.current_token.span.start;
+        let doc = self.take_doc();
 
         self.next_token(); // consume the fn token
 
@@ -336,10 +671,26 @@ impl<'a> Parser<'a> {
 
         let params = self.parse_function_params()?;
 
+        Ok(Signature {
+            name: function_name,
+            params,
+            doc,
+            span: Span {
+                start,
+                end: self.current_token.span.end,
+            },
+        })
+    }
+
+    fn parse_function_body(&mut self, signature: Signature) -> Result<Function, ParseError> {
         // we used current_token_is because we don't want to consume it,
         // we pass this statement that is inside a brace to parse_block_statement.
         if self.current_token_is(TokenKind::LeftBrace) {
-            let body = Box::new(self.parse_block_statement()?);
+            // a break in the body can't leave the loops around the function
+            let loops = std::mem::take(&mut self.loops);
+            let body = self.parse_block_statement();
+            self.loops = loops;
+            let body = Box::new(body?);
 
             if !self.current_token_is(TokenKind::RightBrace) {
                 return Err("expected to close the statement with a right brace".to_string());
@@ -351,88 +702,346 @@ impl<'a> Parser<'a> {
 
             let end = self.current_token.span.end;
 
-            return Ok(Statement::Function(Function {
-                name: function_name,
-                params,
+            return Ok(Function {
+                name: signature.name,
+                params: signature.params,
                 body,
-                span: Span { start, end },
-            }));
+                doc: signature.doc,
+                span: Span {
+                    start: signature.span.start,
+                    end,
+                },
+            });
         }
 
         Err("expected to close the block with a right brace.".to_string())
     }
 
-    fn parse_return_statement(&mut self) -> Result<Statement, ParseError> {
+    fn parse_type_name(&mut self) -> Result<Identifier, ParseError> {
+        match self.current_token.kind.clone() {
+            TokenKind::Identifier { name } => Ok(Identifier {
+                name,
+                span: self.current_token.span.clone(),
+            }),
+            _ => Err(format!(
+                "expected an identifier as the name of the type but got: {}",
+                self.current_token.kind
+            )),
+        }
+    }
+
+    fn parse_struct_statement(&mut self) -> Result<Statement, ParseError> {
         let start = self.current_token.span.start;
-        self.next_token(); // consume return token
+        let doc = self.take_doc();
+        self.next_token(); // consume struct token
 
-        let argument = self.parse_expression(Precedence::Lowest)?.0;
+        let name = self.parse_type_name()?;
 
-        if self.peek_token_is(TokenKind::Semicolon) {
-            self.next_token();
+        self.expect_peek(TokenKind::LeftBrace)?;
+        self.next_token(); // consume the left brace
+
+        let mut fields: Vec<Identifier> = Vec::new();
+
+        while !self.current_token_is(TokenKind::RightBrace) {
+            match self.current_token.kind.clone() {
+                TokenKind::Identifier { name } => {
+                    fields.push(Identifier {
+                        name,
+                        span: self.current_token.span.clone(),
+                    });
+                }
+                _ => {
+                    return Err(format!(
+                        "expected an identifier as the name of the field but got: {}",
+                        self.current_token.kind
+                    ))
+                }
+            }
+
+            self.next_token(); // consume the field
+
+            if self.current_token_is(TokenKind::Comma) {
+                self.next_token();
+            } else if !self.current_token_is(TokenKind::RightBrace) {
+                return Err(format!(
+                    "expected comma or right brace after the field but got: {}",
+                    self.current_token.kind
+                ));
+            }
         }
 
         let end = self.current_token.span.end;
 
-        Ok(Statement::Return(Return {
-            argument,
+        if self.peek_token_is(TokenKind::Semicolon) {
+            self.next_token();
+        }
+
+        Ok(Statement::Struct(Struct {
+            name,
+            fields,
+            doc,
             span: Span { start, end },
         }))
     }
 
-    fn parse_block_statement(&mut self) -> Result<BlockStatement, ParseError> {
+    fn parse_impl_statement(&mut self) -> Result<Statement, ParseError> {
         let start = self.current_token.span.start;
-        self.next_token();
+        self.next_token(); // consume impl token
 
-        let mut block_statement: Vec<Statement> = Vec::new();
+        let mut name = self.parse_type_name()?;
+        let mut trait_name = None;
 
-        while !self.current_token_is(TokenKind::RightBrace)
-            && !self.current_token_is(TokenKind::EOF)
-        {
-            let statement = self.parse_statement()?;
-            block_statement.push(statement);
-            self.next_token();
+        // impl Trait for Struct { ... }
+        if self.peek_token_is(TokenKind::For) {
+            self.next_token(); // consume the name of the trait
+            self.next_token(); // consume for token
+
+            trait_name = Some(name);
+            name = self.parse_type_name()?;
+        }
+
+        self.expect_peek(TokenKind::LeftBrace)?;
+        self.next_token(); // consume the left brace
+
+        let mut methods: Vec<Function> = Vec::new();
+
+        while !self.current_token_is(TokenKind::RightBrace) {
+            if !self.current_token_is(TokenKind::Function) {
+                return Err(format!(
+                    "expected a method declaration in impl block but got: {}",
+                    self.current_token.kind
+                ));
+            }
+
+            if let Statement::Function(method) = self.parse_function_statement()? {
+                methods.push(method);
+            }
+
+            self.next_token(); // consume the right brace of the method
         }
 
         let end = self.current_token.span.end;
 
-        Ok(BlockStatement {
-            body: block_statement,
+        if self.peek_token_is(TokenKind::Semicolon) {
+            self.next_token();
+        }
+
+        Ok(Statement::Impl(Impl {
+            name,
+            trait_name,
+            methods,
             span: Span { start, end },
-        })
+        }))
     }
 
-    fn parse_if_statement(&mut self) -> Result<Statement, ParseError> {
+    fn parse_trait_statement(&mut self) -> Result<Statement, ParseError> {
         let start = self.current_token.span.start;
+        let doc = self.take_doc();
+        self.next_token(); // consume trait token
 
-        self.expect_current(TokenKind::If)?;
-        self.expect_current(TokenKind::LeftParen)?;
-
-        let (condition, _) = self.parse_expression(Precedence::Lowest)?;
+        let name = self.parse_type_name()?;
 
-        self.expect_peek(TokenKind::RightParen)?;
         self.expect_peek(TokenKind::LeftBrace)?;
+        self.next_token(); // consume the left brace
 
-        let mut branches: Vec<If> = Vec::new();
-        let mut alternate: Option<Box<BlockStatement>> = None;
+        let mut required: Vec<Signature> = Vec::new();
+        let mut methods: Vec<Function> = Vec::new();
 
-        let consequent = Box::new(self.parse_block_statement()?);
+        while !self.current_token_is(TokenKind::RightBrace) {
+            if !self.current_token_is(TokenKind::Function) {
+                return Err(format!(
+                    "expected a method declaration in trait block but got: {}",
+                    self.current_token.kind
+                ));
+            }
 
-        self.expect_current(TokenKind::RightBrace)?;
+            let signature = self.parse_function_signature()?;
 
-        while self.current_token_is(TokenKind::Else) {
-            self.next_token(); // consume else token
+            // a method without a body ends with a semicolon
+            if self.current_token_is(TokenKind::Semicolon) {
+                required.push(signature);
+            } else {
+                methods.push(self.parse_function_body(signature)?);
+            }
+
+            self.next_token(); // consume the semicolon or the right brace of the method
+        }
+
+        let end = self.current_token.span.end;
+
+        if self.peek_token_is(TokenKind::Semicolon) {
+            self.next_token();
+        }
+
+        Ok(Statement::Trait(Trait {
+            name,
+            required,
+            methods,
+            doc,
+            span: Span { start, end },
+        }))
+    }
+
+    fn parse_return_statement(&mut self) -> Result<Statement, ParseError> {
+        let start = self.current_token.span.start;
+        self.next_token(); // consume return token
+
+        let (mut argument, span) = self.parse_expression(Precedence::Lowest)?;
+
+        // ret a, b; returns the values as a tuple
+        if self.peek_token_is(TokenKind::Comma) {
+            let mut elements = vec![argument];
+
+            while self.peek_token_is(TokenKind::Comma) {
+                self.next_token(); // consume the current expression
+                self.next_token(); // consume the comma
+
+                elements.push(self.parse_expression(Precedence::Lowest)?.0);
+            }
+
+            argument = Expression::Tuple(Tuple {
+                elements,
+                span: Span {
+                    start: span.start,
+                    end: self.current_token.span.end,
+                },
+            });
+        }
+
+        if self.peek_token_is(TokenKind::Semicolon) {
+            self.next_token();
+        }
+
+        let end = self.current_token.span.end;
+
+        Ok(Statement::Return(Return {
+            argument,
+            span: Span { start, end },
+        }))
+    }
+
+    fn parse_defer_statement(&mut self) -> Result<Statement, ParseError> {
+        let start = self.current_token.span.start;
+        self.next_token(); // consume defer token
+
+        let argument = self.parse_expression(Precedence::Lowest)?.0;
+
+        if self.peek_token_is(TokenKind::Semicolon) {
+            self.next_token();
+        }
+
+        let end = self.current_token.span.end;
+
+        Ok(Statement::Defer(Defer {
+            argument,
+            span: Span { start, end },
+        }))
+    }
+
+    fn parse_yield_statement(&mut self) -> Result<Statement, ParseError> {
+        let start = self.current_token.span.start;
+        self.next_token(); // consume yield token
+
+        let argument = self.parse_expression(Precedence::Lowest)?.0;
+
+        if self.peek_token_is(TokenKind::Semicolon) {
+            self.next_token();
+        }
+
+        let end = self.current_token.span.end;
+
+        Ok(Statement::Yield(Yield {
+            argument,
+            span: Span { start, end },
+        }))
+    }
+
+    fn parse_block_statement(&mut self) -> Result<BlockStatement, ParseError> {
+        let start = self.current_token.span.start;
+        self.next_token();
+
+        self.parse_block_body(start, Vec::new())
+    }
+
+    // Parses the rest of the statements of a block until its closing brace.
+    fn parse_block_body(
+        &mut self,
+        start: usize,
+        mut block_statement: Vec<Statement>,
+    ) -> Result<BlockStatement, ParseError> {
+        while !self.current_token_is(TokenKind::RightBrace)
+            && !self.current_token_is(TokenKind::EOF)
+        {
+            let statement = self.parse_statement()?;
+            block_statement.push(statement);
+            self.next_token();
+        }
+
+        let end = self.current_token.span.end;
+
+        Ok(BlockStatement {
+            body: block_statement,
+            span: Span { start, end },
+        })
+    }
+
+    // A statement starting with a left brace is a block, unless it turns out to be a hash.
+    fn parse_bare_block_statement(&mut self) -> Result<Statement, ParseError> {
+        // an empty pair of braces is a hash in an expression, but an empty block here
+        let expr = if self.peek_token_is(TokenKind::RightBrace) {
+            Expression::Block(self.parse_closed_block()?)
+        } else {
+            self.parse_brace_expression()?
+        };
+
+        if self.peek_token_is(TokenKind::Semicolon) {
+            self.next_token();
+        }
+
+        match expr {
+            Expression::Block(block) => Ok(Statement::Block(block)),
+            expr => Ok(Statement::Expression(expr)),
+        }
+    }
+
+    fn parse_if_statement(&mut self) -> Result<Statement, ParseError> {
+        let if_stmt = self.parse_if()?;
+
+        if self.peek_token_is(TokenKind::Semicolon) {
+            self.next_token();
+        }
+
+        Ok(Statement::If(if_stmt))
+    }
+
+    // Parses an if along with its else-if branches and the alternate block,
+    // the current token is left on the right brace of the last block.
+    fn parse_if(&mut self) -> Result<If, ParseError> {
+        let start = self.current_token.span.start;
+
+        self.expect_current(TokenKind::If)?;
+
+        // parentheses around the condition are optional since they're parsed as a grouped expression
+        let (condition, _) = self.parse_expression(Precedence::Lowest)?;
+
+        self.expect_peek(TokenKind::LeftBrace)?;
+
+        let mut branches: Vec<If> = Vec::new();
+        let mut alternate: Option<Box<BlockStatement>> = None;
+
+        let consequent = Box::new(self.parse_closed_block()?);
+
+        while self.peek_token_is(TokenKind::Else) {
+            self.next_token(); // consume the right brace
+            self.next_token(); // consume else token
 
             // lets parse branches
             if self.current_token_is(TokenKind::If) {
-                self.next_token(); // consume if token
                 let start = self.current_token.span.start;
-                self.expect_current(TokenKind::LeftParen)?;
+                self.next_token(); // consume if token
                 let (condition, _) = self.parse_expression(Precedence::Lowest)?;
-                self.expect_peek(TokenKind::RightParen)?; // biggening of the block
                 self.expect_peek(TokenKind::LeftBrace)?; // biggening of the block
-                let consequent = Box::new(self.parse_block_statement()?);
-                self.expect_current(TokenKind::RightBrace)?; // end of the block
+                let consequent = Box::new(self.parse_closed_block()?);
                 let end = self.current_token.span.end;
 
                 branches.push(If {
@@ -449,23 +1058,31 @@ impl<'a> Parser<'a> {
                     return Err("expected to open the block with left brace".to_string());
                 }
 
-                alternate = Some(Box::new(self.parse_block_statement()?));
-
-                if !self.current_token_is(TokenKind::RightBrace) {
-                    return Err("expected to close the block with right brace".to_string());
-                }
+                alternate = Some(Box::new(self.parse_closed_block()?));
+                break;
             }
         }
 
         let end = self.current_token.span.end;
 
-        Ok(Statement::If(If {
+        Ok(If {
             condition,
             consequent,
             branches,
             alternate,
             span: Span { start, end },
-        }))
+        })
+    }
+
+    // Parses a block statement and makes sure it's closed with a right brace.
+    fn parse_closed_block(&mut self) -> Result<BlockStatement, ParseError> {
+        let block = self.parse_block_statement()?;
+
+        if !self.current_token_is(TokenKind::RightBrace) {
+            return Err("expected to close the block with right brace".to_string());
+        }
+
+        Ok(block)
     }
 
     fn parse_expression_statement(&mut self) -> Result<Statement, ParseError> {
@@ -497,6 +1114,432 @@ impl<'a> Parser<'a> {
         }))
     }
 
+    fn parse_method_call_expression(
+        &mut self,
+        receiver: Expression,
+        receiver_start: usize,
+    ) -> Result<Expression, ParseError> {
+        self.next_token(); // consume the dot
+
+        let method = match self.current_token.kind.clone() {
+            TokenKind::Identifier { name } => Identifier {
+                name,
+                span: self.current_token.span.clone(),
+            },
+            _ => {
+                return Err(format!(
+                    "expected the name of a method or field after the dot but got: {}",
+                    self.current_token.kind
+                ))
+            }
+        };
+
+        // a dot that isn't followed by a call reads a field
+        if !self.peek_token_is(TokenKind::LeftParen) {
+            return Ok(Expression::Field(Field {
+                receiver: Box::new(receiver),
+                span: Span {
+                    start: receiver_start,
+                    end: method.span.end,
+                },
+                field: method,
+            }));
+        }
+
+        self.next_token(); // consume the name of the method
+
+        let (arguments, span) = self.parse_expression_series(TokenKind::RightParen)?;
+
+        Ok(Expression::MethodCall(MethodCall {
+            receiver: Box::new(receiver),
+            method,
+            arguments,
+            span: Span {
+                start: receiver_start,
+                end: span.end,
+            },
+        }))
+    }
+
+    fn parse_index_expression(
+        &mut self,
+        left: Expression,
+        left_start: usize,
+    ) -> Result<Expression, ParseError> {
+        self.next_token(); // consume the left bracket
+
+        // xs[:end] starts with the colon of the slice
+        let start = if self.current_token_is(TokenKind::Colon) {
+            None
+        } else {
+            let (index, _) = self.parse_expression(Precedence::Lowest)?;
+
+            if !self.peek_token_is(TokenKind::Colon) {
+                self.expect_peek(TokenKind::RightBracket)?;
+
+                return Ok(Expression::Index(Index {
+                    left: Box::new(left),
+                    index: Box::new(index),
+                    span: Span {
+                        start: left_start,
+                        end: self.current_token.span.end,
+                    },
+                }));
+            }
+
+            self.next_token(); // consume the start of the slice
+            Some(Box::new(index))
+        };
+
+        let end = self.parse_slice_bound()?;
+
+        let step = if self.peek_token_is(TokenKind::Colon) {
+            self.next_token(); // consume the end of the slice
+            self.parse_slice_bound()?
+        } else {
+            None
+        };
+
+        self.expect_peek(TokenKind::RightBracket)?;
+
+        Ok(Expression::Slice(Slice {
+            left: Box::new(left),
+            start,
+            end,
+            step,
+            span: Span {
+                start: left_start,
+                end: self.current_token.span.end,
+            },
+        }))
+    }
+
+    // Parses the part of a slice after the colon, which is left out when the
+    // next colon or the closing bracket follows right away.
+    fn parse_slice_bound(&mut self) -> Result<Option<Box<Expression>>, ParseError> {
+        if self.peek_token_is(TokenKind::Colon) || self.peek_token_is(TokenKind::RightBracket) {
+            return Ok(None);
+        }
+
+        self.next_token(); // consume the colon
+        let (bound, _) = self.parse_expression(Precedence::Lowest)?;
+
+        Ok(Some(Box::new(bound)))
+    }
+
+    fn parse_assignment_expression(
+        &mut self,
+        target: Expression,
+        target_start: usize,
+    ) -> Result<Expression, ParseError> {
+        if !matches!(
+            target,
+            Expression::Identifier(_) | Expression::Index(_) | Expression::Field(_)
+        ) {
+            return Err(format!("invalid assignment target: {}", target));
+        }
+
+        self.next_token(); // consume the assign token
+
+        // assignments are right-associative, so a = b = 1 assigns 1 to both
+        let (value, span) = self.parse_expression(Precedence::Lowest)?;
+
+        Ok(Expression::Assignment(Assignment {
+            target: Box::new(target),
+            value: Box::new(value),
+            span: Span {
+                start: target_start,
+                end: span.end,
+            },
+        }))
+    }
+
+    fn parse_conditional_expression(
+        &mut self,
+        condition: Expression,
+        condition_start: usize,
+    ) -> Result<Expression, ParseError> {
+        self.next_token(); // consume the question mark
+
+        let (consequent, _) = self.parse_expression(Precedence::Lowest)?;
+
+        self.expect_peek(TokenKind::Colon)?;
+        self.next_token(); // consume the colon
+
+        // parsing the alternate with a lower precedence makes nested conditionals right-associative
+        let (alternate, span) = self.parse_expression(Precedence::Assign)?;
+
+        Ok(Expression::Conditional(Conditional {
+            condition: Box::new(condition),
+            consequent: Box::new(consequent),
+            alternate: Box::new(alternate),
+            span: Span {
+                start: condition_start,
+                end: span.end,
+            },
+        }))
+    }
+
+    fn parse_array_expression(&mut self) -> Result<Expression, ParseError> {
+        let start = self.current_token.span.start;
+
+        if self.peek_token_is(TokenKind::RightBracket) {
+            let (elements, span) = self.parse_expression_series(TokenKind::RightBracket)?;
+            return Ok(Expression::Array(Array { elements, span }));
+        }
+
+        self.next_token(); // consume the left bracket
+
+        let element = self.parse_series_element()?;
+
+        if self.peek_token_is(TokenKind::For) {
+            self.next_token(); // consume the element
+            let comprehension = self.parse_comprehension()?;
+            self.expect_peek(TokenKind::RightBracket)?;
+
+            return Ok(Expression::ListComprehension(Box::new(ListComprehension {
+                element: Box::new(element),
+                comprehension,
+                span: Span {
+                    start,
+                    end: self.current_token.span.end,
+                },
+            })));
+        }
+
+        let (elements, span) =
+            self.parse_expression_series_rest(start, vec![element], TokenKind::RightBracket)?;
+
+        Ok(Expression::Array(Array { elements, span }))
+    }
+
+    // Parentheses group an expression unless they hold a comma, so (a) is a
+    // grouping while (a,) and (a, b) are tuples.
+    fn parse_paren_expression(&mut self) -> Result<Expression, ParseError> {
+        let start = self.current_token.span.start;
+
+        if self.peek_token_is(TokenKind::RightParen) {
+            self.next_token(); // consume the left paren
+
+            return Ok(Expression::Tuple(Tuple {
+                elements: vec![],
+                span: Span {
+                    start,
+                    end: self.current_token.span.end,
+                },
+            }));
+        }
+
+        self.next_token(); // consume the left paren
+        let expr = self.parse_series_element()?;
+
+        if !self.peek_token_is(TokenKind::Comma) {
+            self.expect_peek(TokenKind::RightParen)?;
+            return Ok(expr);
+        }
+
+        let (elements, span) =
+            self.parse_expression_series_rest(start, vec![expr], TokenKind::RightParen)?;
+
+        Ok(Expression::Tuple(Tuple { elements, span }))
+    }
+
+    // #{a, b} is a set, the hashtag tells it apart from a hash or a block.
+    fn parse_set_expression(&mut self) -> Result<Expression, ParseError> {
+        let start = self.current_token.span.start;
+        self.next_token(); // consume the hashtag
+
+        let (elements, span) = self.parse_expression_series(TokenKind::RightBrace)?;
+
+        Ok(Expression::Set(Set {
+            elements,
+            span: Span {
+                start,
+                end: span.end,
+            },
+        }))
+    }
+
+    // A left brace in an expression opens either a block or a hash, a hash is
+    // told apart by the colon that follows its first key.
+    fn parse_brace_expression(&mut self) -> Result<Expression, ParseError> {
+        let start = self.current_token.span.start;
+
+        if self.peek_token_is(TokenKind::RightBrace) {
+            self.next_token(); // consume the left brace
+
+            return Ok(Expression::Hash(Hash {
+                entries: vec![],
+                span: Span {
+                    start,
+                    end: self.current_token.span.end,
+                },
+            }));
+        }
+
+        if matches!(
+            self.peek_token.kind,
+            TokenKind::Hashtag
+                | TokenKind::Const
+                | TokenKind::Function
+                | TokenKind::Return
+                | TokenKind::Yield
+                | TokenKind::Defer
+                | TokenKind::For
+                | TokenKind::If
+                | TokenKind::Struct
+                | TokenKind::Impl
+                | TokenKind::Trait
+                | TokenKind::Macro
+                | TokenKind::Break
+                | TokenKind::Continue
+                | TokenKind::LeftBrace
+        ) {
+            return Ok(Expression::Block(self.parse_closed_block()?));
+        }
+
+        self.next_token(); // consume the left brace
+
+        // a spread can only start a hash, since a block has no use for it
+        let first_entry = if self.current_token_is(TokenKind::Ellipsis) {
+            HashEntry::Spread(self.parse_spread()?)
+        } else {
+            let (first, _) = self.parse_expression(Precedence::Lowest)?;
+
+            if !self.peek_token_is(TokenKind::Colon) {
+                if self.peek_token_is(TokenKind::Semicolon) {
+                    self.next_token();
+                }
+
+                return self.parse_rest_of_brace_block(start, Statement::Expression(first));
+            }
+
+            self.next_token(); // consume the key
+            self.next_token(); // consume the colon
+
+            // no value starts with for, so the key was the label of a loop in a block
+            if let (Expression::Identifier(label), TokenKind::For) =
+                (&first, &self.current_token.kind)
+            {
+                let label = label.clone();
+                let labeled_loop = self.parse_for_statement(Some(label))?;
+
+                return self.parse_rest_of_brace_block(start, labeled_loop);
+            }
+
+            let (value, _) = self.parse_expression(Precedence::Lowest)?;
+
+            if self.peek_token_is(TokenKind::For) {
+                self.next_token(); // consume the value
+                let comprehension = self.parse_comprehension()?;
+                self.expect_peek(TokenKind::RightBrace)?;
+
+                return Ok(Expression::HashComprehension(Box::new(HashComprehension {
+                    key: Box::new(first),
+                    value: Box::new(value),
+                    comprehension,
+                    span: Span {
+                        start,
+                        end: self.current_token.span.end,
+                    },
+                })));
+            }
+
+            HashEntry::Pair(first, value)
+        };
+
+        let mut entries = vec![first_entry];
+
+        while self.peek_token_is(TokenKind::Comma) {
+            self.next_token(); // consume the value
+
+            if self.peek_token_is(TokenKind::RightBrace) {
+                break;
+            }
+
+            self.next_token(); // consume the comma
+
+            if self.current_token_is(TokenKind::Ellipsis) {
+                entries.push(HashEntry::Spread(self.parse_spread()?));
+                continue;
+            }
+
+            let (key, _) = self.parse_expression(Precedence::Lowest)?;
+
+            self.expect_peek(TokenKind::Colon)?;
+            self.next_token(); // consume the colon
+
+            let (value, _) = self.parse_expression(Precedence::Lowest)?;
+
+            entries.push(HashEntry::Pair(key, value));
+        }
+
+        self.expect_peek(TokenKind::RightBrace)?;
+
+        Ok(Expression::Hash(Hash {
+            entries,
+            span: Span {
+                start,
+                end: self.current_token.span.end,
+            },
+        }))
+    }
+
+    // Parses the statements of a brace expression that turned out to be a block
+    // after its first statement.
+    fn parse_rest_of_brace_block(
+        &mut self,
+        start: usize,
+        first: Statement,
+    ) -> Result<Expression, ParseError> {
+        self.next_token(); // consume the first statement
+
+        let block = self.parse_block_body(start, vec![first])?;
+
+        if !self.current_token_is(TokenKind::RightBrace) {
+            return Err("expected to close the block with right brace".to_string());
+        }
+
+        Ok(Expression::Block(block))
+    }
+
+    // Parses the `for x in xs if condition` clause of a comprehension.
+    fn parse_comprehension(&mut self) -> Result<Comprehension, ParseError> {
+        self.next_token(); // consume for token
+
+        let variable = match self.current_token.kind.clone() {
+            TokenKind::Identifier { name } => Identifier {
+                name,
+                span: self.current_token.span.clone(),
+            },
+            _ => {
+                return Err(format!(
+                    "expected an identifier as the variable of the comprehension but got: {}",
+                    self.current_token.kind
+                ))
+            }
+        };
+
+        self.expect_peek(TokenKind::In)?;
+        self.next_token(); // consume in token
+
+        let (iterable, _) = self.parse_expression(Precedence::Lowest)?;
+
+        let mut condition = None;
+        if self.peek_token_is(TokenKind::If) {
+            self.next_token(); // consume the iterable
+            self.next_token(); // consume if token
+
+            condition = Some(Box::new(self.parse_expression(Precedence::Lowest)?.0));
+        }
+
+        Ok(Comprehension {
+            variable,
+            iterable: Box::new(iterable),
+            condition,
+        })
+    }
+
     fn parse_bool_expression(&mut self, token_kind: TokenKind) -> Result<Expression, ParseError> {
         let bool_literal = Expression::Literal(Literal::Boolean(Boolean {
             raw: token_kind == TokenKind::True,
@@ -620,10 +1663,22 @@ impl<'a> Parser<'a> {
             TokenKind::Integer(value) => {
                 Expression::Literal(Literal::Integer(Integer { raw: *value, span }))
             }
+            TokenKind::BigInteger(value) => Expression::Literal(Literal::BigInteger(BigInteger {
+                raw: value.clone(),
+                span,
+            })),
+            TokenKind::Decimal(value) => Expression::Literal(Literal::Decimal(Decimal {
+                raw: value.clone(),
+                span,
+            })),
             TokenKind::String(value) => Expression::Literal(Literal::String(StringType {
                 raw: value.clone(),
                 span,
             })),
+            TokenKind::Bytes(value) => Expression::Literal(Literal::Bytes(Bytes {
+                raw: value.clone(),
+                span,
+            })),
             token_kind @ TokenKind::True | token_kind @ TokenKind::False => {
                 return self.parse_bool_expression(token_kind.clone());
             }
@@ -644,11 +1699,12 @@ impl<'a> Parser<'a> {
                     },
                 })
             }
-            TokenKind::LeftParen => {
-                self.next_token();
-                let expr = self.parse_expression(Precedence::Lowest)?.0;
-                self.expect_peek(TokenKind::RightParen)?;
-                return Ok(expr);
+            TokenKind::LeftParen => return self.parse_paren_expression(),
+            TokenKind::LeftBracket => return self.parse_array_expression(),
+            TokenKind::If => Expression::If(Box::new(self.parse_if()?)),
+            TokenKind::LeftBrace => return self.parse_brace_expression(),
+            TokenKind::Hashtag if self.peek_token_is(TokenKind::LeftBrace) => {
+                return self.parse_set_expression()
             }
             _ => {
                 return Err(format!(
@@ -678,6 +1734,9 @@ impl<'a> Parser<'a> {
             | TokenKind::LessThan
             | TokenKind::GreaterEqual
             | TokenKind::GreaterThan
+            | TokenKind::In
+            | TokenKind::Pipe
+            | TokenKind::Ampersand
             | TokenKind::Identifier { .. } => {
                 self.next_token(); // consume the first part of the expression
 
@@ -687,7 +1746,10 @@ impl<'a> Parser<'a> {
 
                 self.next_token(); // consume the operator
 
-                let (right, span) = self.parse_expression(precedence).unwrap();
+                let (right, span) = match self.parse_expression(precedence) {
+                    Ok(right) => right,
+                    Err(e) => return Some(Err(e)),
+                };
 
                 Some(Ok(Expression::Infix(BinaryExpression {
                     operator,
@@ -703,8 +1765,22 @@ impl<'a> Parser<'a> {
                 self.next_token(); // consume the identifier token
                 Some(self.parse_function_call_expression(left, left_start))
             }
-
-            // TODO - Implement array index epxression parser
+            TokenKind::Dot => {
+                self.next_token(); // consume the receiver
+                Some(self.parse_method_call_expression(left, left_start))
+            }
+            TokenKind::LeftBracket => {
+                self.next_token(); // consume the indexed expression
+                Some(self.parse_index_expression(left, left_start))
+            }
+            TokenKind::Assign => {
+                self.next_token(); // consume the assignment target
+                Some(self.parse_assignment_expression(left, left_start))
+            }
+            TokenKind::Question => {
+                self.next_token(); // consume the condition
+                Some(self.parse_conditional_expression(left, left_start))
+            }
             _ => None,
         }
     }
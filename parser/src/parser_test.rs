@@ -47,6 +47,35 @@ mod tests {
         assert_parse("#my_var = 1 + 2 * 3;");
     }
 
+    #[test]
+    fn test_constant_declaration() {
+        assert_parse("const MY_CONST = 1 + 2 * 3;");
+    }
+
+    #[test]
+    fn test_assignment_expression() {
+        assert_parse("my_var = 1 + 2;");
+        assert_parse("a = b = 1;");
+        assert_parse("xs[0] = m[\"k\"] = 5;");
+        assert_parse("p.x = 1;");
+        assert!(Parser::parse("1 = 2;".to_string()).is_err());
+        assert!(Parser::parse("f() = 2;".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_parse_errors_are_returned() {
+        assert!(Parser::parse("1 +".to_string()).is_err());
+        assert!(Parser::parse("#x = 0b12;".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_lexer_error_is_reported_once() {
+        for input in ["#x = 0b12;", "println(1__0 + 2);", "b\"\\xZZ\" + 1; 2"] {
+            let errors = Parser::parse(input.to_string()).unwrap_err();
+            assert_eq!(errors.len(), 1, "{:?}", errors);
+        }
+    }
+
     #[test]
     fn test_if_statement() {
         assert_parse(
@@ -65,6 +94,13 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_if_expression() {
+        assert_parse("#x = if c { 1 } else { 2 };");
+        assert_parse("#x = if (a) { 1 } else if b { 2 };");
+        assert_parse("#y = c ? 1 : 2;");
+    }
+
     #[test]
     fn test_if_statement2() {
         assert_parse(
@@ -85,11 +121,63 @@ mod tests {
         println!("{:#?}", block);
     }
 
+    #[test]
+    fn test_bare_block_statement() {
+        assert_parse("{ #a = 1; print(a); }");
+        assert_parse("{ { #a = 1; }; }");
+    }
+
+    #[test]
+    fn test_method_call_expression() {
+        assert_parse("\"abc\".len();");
+        assert_parse("xs.push(1).len();");
+        assert!(Parser::parse("xs.1()".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_array_and_index_expression() {
+        assert_parse("[1, 2, 3][0];");
+        assert_parse("matrix[i][j];");
+    }
+
+    #[test]
+    fn test_slice_expression() {
+        assert_parse("s[1:4];");
+        assert_parse("xs[:-1][::2];");
+        assert_parse("xs[n - 1::-1];");
+        assert!(Parser::parse("xs[1:2:3:4]".to_string()).is_err());
+    }
+
     #[test]
     fn test_return_statement() {
         assert_parse("ret 1 + 2");
     }
 
+    #[test]
+    fn test_defer_statement() {
+        assert_parse("fn f() { defer close(file); }");
+    }
+
+    #[test]
+    fn test_trait_statement() {
+        assert_parse("trait Shape { fn area(self); fn describe(self) { self.area() } }");
+        assert_parse("impl Shape for Square { fn area(self) { self.side * self.side } }");
+    }
+
+    #[test]
+    fn test_tuples() {
+        assert_parse("(1, 2,)");
+        assert_parse("ret a, b;");
+        assert_parse("#(q, r) = divmod(7, 2);");
+    }
+
+    #[test]
+    fn test_macro_statement() {
+        assert_parse(
+            "macro unless(c, a, b) { quote(if !(unquote(c)) { unquote(a) } else { unquote(b) }) }",
+        );
+    }
+
     #[test]
     fn test_parse_function_params() {
         let mut lexer = Lexer::new("(a, b, c)".to_string());
@@ -121,6 +209,22 @@ mod tests {
         assert_parse("foo_bar();");
     }
 
+    #[test]
+    fn test_spread_expression() {
+        assert_parse("f(...args, 1);");
+        assert_parse("[...a, ...b];");
+        assert_parse("{ ...defaults, \"k\": v };");
+        assert!(Parser::parse("{ \"k\": v, ... };".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_set_expression() {
+        assert_parse("#{1, 2, ...xs};");
+        assert_parse("#{};");
+        assert_parse("x in a | b & c;");
+        assert!(Parser::parse("#{1: 2};".to_string()).is_err());
+    }
+
     #[test]
     fn test_parse_1() {
         assert_parse(
@@ -150,6 +254,14 @@ mod tests {
         assert_parse("3 * 2");
     }
 
+    #[test]
+    fn test_malformed_number_literals() {
+        assert_parse("0xff + 0o17 + 0b1010 + 1_000");
+        assert!(Parser::parse("0b102".to_string()).is_err());
+        assert!(Parser::parse("1__0".to_string()).is_err());
+        assert!(Parser::parse("0x".to_string()).is_err());
+    }
+
     #[test]
     fn test_comparative_expression() {
         assert_parse("i < 10;");
@@ -179,6 +291,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_for_in_statement() {
+        assert_parse("for x in [1, 2, 3] { print(x); }");
+        assert_parse("for ch in \"abc\".upper() { print(ch); }");
+    }
+
+    #[test]
+    fn test_parse_break_and_continue() {
+        assert_parse("for x in xs { if x { break; } continue }");
+        assert_parse("outer: for x in xs { for #i = 0; i < x; i++ { break outer; } }");
+        assert_parse("outer: for x in xs { inner: for y in ys { continue outer; } }");
+
+        let fails = |input: &str| Parser::parse(input.to_string()).unwrap_err()[0].clone();
+        assert_eq!(fails("break;"), "break can only be used inside of a loop");
+        assert_eq!(
+            fails("for x in xs { continue inner; }"),
+            "unknown loop label inner"
+        );
+        assert_eq!(
+            fails("outer: for x in xs { fn f() { break outer; } }"),
+            "unknown loop label outer"
+        );
+        assert_eq!(
+            fails("outer: if x { }"),
+            "expected a loop after the label outer but got: if"
+        );
+    }
+
+    #[test]
+    fn test_parse_comprehensions() {
+        assert_parse("[x * 2 for x in xs if x > 0]");
+        assert_parse("{x: x * x for x in [1, 2, 3]}");
+        assert_parse("#h = {\"a\": 1, \"b\": 2,}; { h; 1 }");
+    }
+
+    #[test]
+    fn test_parse_struct_and_impl() {
+        assert_parse("struct Vector { x, y }");
+        assert_parse(
+            "impl Vector { fn add(self, other) { Vector(self.x + other.x, self.y + other.y) } }",
+        );
+    }
+
+    #[test]
+    fn test_parse_yield_statement() {
+        assert_parse("fn gen() { yield 1; yield 2 }");
+    }
+
     #[test]
     fn test_identifier_and_comparative() {
         assert_parse(
@@ -5,8 +5,12 @@ use token::TokenKind;
 #[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
 pub enum Precedence {
     Lowest,
+    Assign,      // =
+    Conditional, // a ? b : c
     Equals,      // ==
     LessGreater, // > or <
+    BitOr,       // | of sets
+    BitAnd,      // & of sets
     Sum,         // + or =
     Product,     // * or /
     Prefix,      // -X or !X
@@ -16,17 +20,23 @@ pub enum Precedence {
 
 pub fn determine_token_precedence(token_kind: TokenKind) -> Precedence {
     match token_kind {
+        TokenKind::Assign => Precedence::Assign,
+        TokenKind::Question => Precedence::Conditional,
         TokenKind::Equal => Precedence::Equals,
         TokenKind::NotEqual => Precedence::Equals,
         TokenKind::LessThan => Precedence::LessGreater,
         TokenKind::LessEqual => Precedence::LessGreater,
         TokenKind::GreaterThan => Precedence::LessGreater,
         TokenKind::GreaterEqual => Precedence::LessGreater,
+        TokenKind::In => Precedence::LessGreater,
+        TokenKind::Pipe => Precedence::BitOr,
+        TokenKind::Ampersand => Precedence::BitAnd,
         TokenKind::Plus => Precedence::Sum,
         TokenKind::Minus => Precedence::Sum,
         TokenKind::Asterisk => Precedence::Product,
         TokenKind::Slash => Precedence::Product,
         TokenKind::LeftParen => Precedence::Call,
+        TokenKind::Dot => Precedence::Call,
         TokenKind::LeftBracket => Precedence::Index,
         _ => Precedence::Lowest,
     }
@@ -36,8 +46,12 @@ impl fmt::Display for Precedence {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Precedence::Lowest => write!(f, "lowest"),
+            Precedence::Assign => write!(f, "assign"),
+            Precedence::Conditional => write!(f, "conditional"),
             Precedence::Equals => write!(f, "equals"),
             Precedence::LessGreater => write!(f, "less_greater"),
+            Precedence::BitOr => write!(f, "bit_or"),
+            Precedence::BitAnd => write!(f, "bit_and"),
             Precedence::Sum => write!(f, "sum"),
             Precedence::Product => write!(f, "product"),
             Precedence::Prefix => write!(f, "prefix"),
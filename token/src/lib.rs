@@ -1,3 +1,4 @@
+use num_bigint::BigInt;
 use std::fmt;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -12,7 +13,12 @@ pub enum TokenKind {
     EOF,
     Identifier { name: String },
     Integer(i64),
+    BigInteger(BigInt),
+    // the digits of a 12.34d literal, without the suffix
+    Decimal(String),
     String(String),
+    Bytes(Vec<u8>),
+    DocComment(String),
 
     // Operators
     Plus,
@@ -35,12 +41,16 @@ pub enum TokenKind {
     LeftBracket,
     RightBracket,
     Comma,
+    Dot,
+    Ellipsis,
     Hashtag,
     DoubleQuote,
     SingleQuote,
     Pipe,
     Ampersand,
     Semicolon,
+    Colon,
+    Question,
     LessThan,
     GreaterThan,
     LessEqual,
@@ -59,27 +69,43 @@ pub enum TokenKind {
     For,
     Break,
     Continue,
+    Const,
+    Yield,
+    In,
+    Struct,
+    Impl,
+    Defer,
+    Trait,
+    Macro,
 }
 
 impl fmt::Display for TokenKind {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Identifier { name } => write!(f, "{}", name),
+            Self::DocComment(doc) => write!(f, "///{}", doc),
             Self::Integer(value) => write!(f, "{}", value),
+            Self::BigInteger(value) => write!(f, "{}", value),
+            Self::Decimal(value) => write!(f, "{}d", value),
             Self::String(value) => write!(f, "{}", value),
+            Self::Bytes(value) => write!(f, "b\"{}\"", value.escape_ascii()),
             Self::Plus => write!(f, "+"),
             Self::Minus => write!(f, "-"),
             Self::Asterisk => write!(f, "*"),
             Self::Slash => write!(f, "/"),
             Self::Modulo => write!(f, "%"),
             Self::Assign => write!(f, "="),
+            Self::Equal => write!(f, "=="),
+            Self::NotEqual => write!(f, "!="),
             Self::LeftParen => write!(f, "("),
             Self::RightParen => write!(f, ")"),
             Self::LeftBrace => write!(f, "{{"),
             Self::RightBrace => write!(f, "}}"),
-            Self::LeftBracket => write!(f, "[["),
-            Self::RightBracket => write!(f, "]]"),
+            Self::LeftBracket => write!(f, "["),
+            Self::RightBracket => write!(f, "]"),
             Self::Comma => write!(f, ","),
+            Self::Dot => write!(f, "."),
+            Self::Ellipsis => write!(f, "..."),
             Self::Hashtag => write!(f, "#"),
             Self::DoubleQuote => write!(f, "\""),
             Self::SingleQuote => write!(f, "'"),
@@ -92,6 +118,8 @@ impl fmt::Display for TokenKind {
             Self::And => write!(f, "&&"),
             Self::Or => write!(f, "||"),
             Self::Semicolon => write!(f, ";"),
+            Self::Colon => write!(f, ":"),
+            Self::Question => write!(f, "?"),
             Self::Function => write!(f, "fn"),
             Self::Match => write!(f, "match"),
             Self::If => write!(f, "if"),
@@ -100,6 +128,14 @@ impl fmt::Display for TokenKind {
             Self::For => write!(f, "for"),
             Self::Break => write!(f, "break"),
             Self::Continue => write!(f, "continue"),
+            Self::Const => write!(f, "const"),
+            Self::Yield => write!(f, "yield"),
+            Self::In => write!(f, "in"),
+            Self::Struct => write!(f, "struct"),
+            Self::Impl => write!(f, "impl"),
+            Self::Defer => write!(f, "defer"),
+            Self::Trait => write!(f, "trait"),
+            Self::Macro => write!(f, "macro"),
             Self::True => write!(f, "true"),
             Self::False => write!(f, "false"),
             Self::Illegal => write!(f, "ILLEGAL"),